@@ -0,0 +1,74 @@
+use std::io::{Cursor, Read};
+
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+const MAX_BANNER_BYTES: u64 = 2 * 1024 * 1024;
+/// Well below any GPU's texture limit, and a few compressed bytes can still
+/// declare an image big enough to exhaust memory once decoded.
+const MAX_BANNER_SIDE: u32 = 2048;
+
+#[derive(Deserialize, Serialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Branding {
+    pub accent_color: Option<String>,
+    pub banner_url: Option<String>,
+}
+
+impl Branding {
+    pub fn accent(&self) -> Option<egui::Color32> {
+        parse_color(self.accent_color.as_deref()?)
+    }
+}
+
+/// Parses `#RRGGBB` (the leading `#` is optional).
+pub fn parse_color(text: &str) -> Option<egui::Color32> {
+    let hex = text.trim().trim_start_matches('#');
+    if hex.len() != 6 {
+        return None;
+    }
+    let value = u32::from_str_radix(hex, 16).ok()?;
+    Some(egui::Color32::from_rgb(
+        (value >> 16) as u8,
+        (value >> 8) as u8,
+        value as u8,
+    ))
+}
+
+pub fn apply_accent(visuals: &mut egui::Visuals, accent: egui::Color32) {
+    visuals.selection.bg_fill = accent;
+    visuals.hyperlink_color = accent;
+    visuals.widgets.hovered.bg_stroke.color = accent;
+    visuals.widgets.active.bg_fill = accent;
+}
+
+pub fn fetch_banner(agent: &ureq::Agent, url: &str) -> Result<egui::ColorImage, String> {
+    let response = agent.get(url).call().map_err(|e| e.to_string())?;
+    let mut bytes = Vec::new();
+    response
+        .into_reader()
+        .take(MAX_BANNER_BYTES + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|e| e.to_string())?;
+    if bytes.len() as u64 > MAX_BANNER_BYTES {
+        return Err("banner image is too large".into());
+    }
+    // The header is read first, decoding only starts for a sensible size.
+    let reader = || {
+        image::io::Reader::new(Cursor::new(&bytes))
+            .with_guessed_format()
+            .map_err(|e| e.to_string())
+    };
+    let (width, height) = reader()?.into_dimensions().map_err(|e| e.to_string())?;
+    if width > MAX_BANNER_SIDE || height > MAX_BANNER_SIDE {
+        return Err(format!(
+            "banner image is {width}x{height}, at most {MAX_BANNER_SIDE}x{MAX_BANNER_SIDE} is shown"
+        ));
+    }
+    let image = reader()?.decode().map_err(|e| e.to_string())?.to_rgba8();
+    let size = [image.width() as usize, image.height() as usize];
+    Ok(egui::ColorImage::from_rgba_unmultiplied(
+        size,
+        image.as_flat_samples().as_slice(),
+    ))
+}
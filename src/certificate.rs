@@ -0,0 +1,147 @@
+use std::{
+    net::{TcpStream, ToSocketAddrs},
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime},
+};
+
+use rustls::{
+    client::{ServerCertVerified, ServerCertVerifier, WebPkiVerifier},
+    Certificate, ClientConfig, ClientConnection, OwnedTrustAnchor, RootCertStore, ServerName,
+};
+
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct CertificateInfo {
+    pub subject: String,
+    pub issuer: String,
+    pub not_after: String,
+    /// `None` once the certificate has expired.
+    pub expires_in: Option<Duration>,
+    /// Whether the certificate chain and hostname pass the same checks the
+    /// launcher's requests use.
+    pub verification: Result<(), String>,
+}
+
+/// Records the regular verification result but accepts any certificate, so
+/// details can still be shown for a certificate that would be rejected. The
+/// connection is closed right after the handshake, nothing is ever sent on it.
+struct InspectingVerifier {
+    inner: WebPkiVerifier,
+    result: Mutex<Option<Result<(), String>>>,
+}
+
+impl ServerCertVerifier for InspectingVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &Certificate,
+        intermediates: &[Certificate],
+        server_name: &ServerName,
+        scts: &mut dyn Iterator<Item = &[u8]>,
+        ocsp_response: &[u8],
+        now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let result = self
+            .inner
+            .verify_server_cert(
+                end_entity,
+                intermediates,
+                server_name,
+                scts,
+                ocsp_response,
+                now,
+            )
+            .map(|_| ())
+            .map_err(|e| e.to_string());
+        *self.result.lock().unwrap() = Some(result);
+        Ok(ServerCertVerified::assertion())
+    }
+}
+
+/// Splits `host:port` with 443 as the default port. IPv6 addresses come in
+/// brackets when they have a port, `[::1]:8443`, and may be bare without one.
+fn split_authority(authority: &str) -> Option<(&str, u16)> {
+    if let Some(rest) = authority.strip_prefix('[') {
+        let (host, rest) = rest.split_once(']')?;
+        return match rest.strip_prefix(':') {
+            Some(port) => Some((host, port.parse().ok()?)),
+            None if rest.is_empty() => Some((host, 443)),
+            None => None,
+        };
+    }
+    match authority.split_once(':') {
+        // More than one colon is a bare IPv6 address.
+        Some((_, rest)) if rest.contains(':') => Some((authority, 443)),
+        Some((host, port)) => Some((host, port.parse().ok()?)),
+        None => Some((authority, 443)),
+    }
+}
+
+/// Performs a TLS handshake with the host of `url` and describes the
+/// certificate it presents.
+pub fn fetch(url: &str) -> Result<CertificateInfo, String> {
+    let authority = url
+        .trim()
+        .strip_prefix("https://")
+        .ok_or("Only https:// hosts have a certificate")?
+        .split('/')
+        .next()
+        .unwrap_or_default();
+    let (host, port) =
+        split_authority(authority).ok_or_else(|| format!("Invalid host or port in {url}"))?;
+
+    let mut roots = RootCertStore::empty();
+    roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|ta| {
+        OwnedTrustAnchor::from_subject_spki_name_constraints(
+            ta.subject,
+            ta.spki,
+            ta.name_constraints,
+        )
+    }));
+    let verifier = Arc::new(InspectingVerifier {
+        inner: WebPkiVerifier::new(roots, None),
+        result: Mutex::new(None),
+    });
+    let config = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_custom_certificate_verifier(verifier.clone())
+        .with_no_client_auth();
+    let server_name = ServerName::try_from(host).map_err(|e| e.to_string())?;
+    let mut connection =
+        ClientConnection::new(Arc::new(config), server_name).map_err(|e| e.to_string())?;
+
+    let address = (host, port)
+        .to_socket_addrs()
+        .map_err(|e| format!("Unable to resolve {host}: {e}"))?
+        .next()
+        .ok_or_else(|| format!("Unable to resolve {host}"))?;
+    let mut socket = TcpStream::connect_timeout(&address, HANDSHAKE_TIMEOUT)
+        .map_err(|e| format!("Unable to connect to {authority}: {e}"))?;
+    socket.set_read_timeout(Some(HANDSHAKE_TIMEOUT)).ok();
+    socket.set_write_timeout(Some(HANDSHAKE_TIMEOUT)).ok();
+    while connection.is_handshaking() {
+        connection
+            .complete_io(&mut socket)
+            .map_err(|e| format!("TLS handshake failed: {e}"))?;
+    }
+
+    let der = connection
+        .peer_certificates()
+        .and_then(|certs| certs.first())
+        .ok_or("The server didn't present a certificate")?;
+    let (_, cert) = x509_parser::parse_x509_certificate(&der.0)
+        .map_err(|e| format!("Unable to parse the certificate: {e}"))?;
+    let validity = cert.validity();
+    let verification = verifier
+        .result
+        .lock()
+        .unwrap()
+        .take()
+        .unwrap_or(Err("The certificate was not verified".into()));
+    Ok(CertificateInfo {
+        subject: cert.subject().to_string(),
+        issuer: cert.issuer().to_string(),
+        not_after: validity.not_after.to_string(),
+        expires_in: validity.time_to_expiration().map(|d| d.unsigned_abs()),
+        verification,
+    })
+}
@@ -0,0 +1,458 @@
+use std::{
+    collections::HashMap,
+    env, fs, io,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{CharacterView, Density, Host, InputLimits, PasswordRetention};
+
+const MAX_RECENT: usize = 10;
+/// Settings that describe this PC rather than the user, never carried over
+/// by an export.
+const MACHINE_LOCAL: [&str; 4] = [
+    "last_run_version",
+    "url_scheme_handler",
+    "window_pos",
+    "window_size",
+];
+
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub host: Host,
+    pub custom_host: String,
+    pub recent_hosts: Vec<String>,
+    pub window_pos: Option<[f32; 2]>,
+    pub window_size: Option<[f32; 2]>,
+    pub dev_mode: bool,
+    pub mhf_folder: String,
+    pub game_workdir: String,
+    pub input_limits: HashMap<String, InputLimits>,
+    pub stay_logged_in: bool,
+    pub session: Option<SavedSession>,
+    pub character_views: HashMap<String, CharacterView>,
+    pub density: Density,
+    pub themes: HashMap<String, ServerTheme>,
+    pub accounts: HashMap<String, Vec<String>>,
+    pub confirm_launch: bool,
+    pub confirm_quit_while_running: bool,
+    pub start_minimized: bool,
+    pub password_retention: PasswordRetention,
+    pub ui_scale: f32,
+    /// Minutes without input before logging out, 0 disables it.
+    pub idle_logout_minutes: u32,
+    /// Minutes between character list refreshes, 0 disables it.
+    pub auto_refresh_minutes: u32,
+    pub gamepad: bool,
+    pub proxy: ProxyMode,
+    pub request_timeout_secs: u64,
+    pub max_response_mib: u64,
+    /// Hash of the last message of the day dismissed on each host.
+    pub seen_motd: HashMap<String, u64>,
+    pub mini_mode: bool,
+    pub discord_presence: bool,
+    pub discord_application_id: String,
+    /// Sent with character creation when set.
+    pub character_defaults: Option<CharacterDefaults>,
+    /// Launcher version of the previous run, for showing release notes once.
+    pub last_run_version: Option<String>,
+    /// Executable the `mhf-launcher://` scheme was last registered for.
+    pub url_scheme_handler: Option<String>,
+    pub endpoints: Endpoints,
+    pub password_reset: HashMap<String, PasswordReset>,
+    pub field_casing: HashMap<String, FieldCasing>,
+}
+
+/// Look remembered for one server, unset fields follow the global settings.
+#[derive(Serialize, Deserialize, Default, Clone, PartialEq)]
+#[serde(default)]
+pub struct ServerTheme {
+    pub dark_mode: Option<bool>,
+    /// `#RRGGBB`, takes precedence over the server's own accent color.
+    pub accent_color: Option<String>,
+    pub density: Option<Density>,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone, Copy)]
+pub struct CharacterDefaults {
+    pub is_female: bool,
+    pub weapon: u32,
+}
+
+/// How the "Forgot password?" link behaves for a server.
+#[derive(Serialize, Deserialize, Default, PartialEq)]
+pub enum PasswordReset {
+    #[default]
+    Disabled,
+    /// Open the server's reset page in the browser.
+    Url(String),
+    /// POST the username to the server's reset endpoint.
+    Request,
+}
+
+/// How a server names the fields of its JSON responses.
+#[derive(Serialize, Deserialize, Default, PartialEq, Clone, Copy)]
+pub enum FieldCasing {
+    #[default]
+    CamelCase,
+    /// `snake_case` keys are converted to `camelCase` before decoding.
+    SnakeCase,
+}
+
+/// Which proxy outgoing requests go through.
+#[derive(Serialize, Deserialize, Default, PartialEq, Clone)]
+pub enum ProxyMode {
+    /// Use `HTTPS_PROXY`/`HTTP_PROXY`/`ALL_PROXY` when set.
+    #[default]
+    System,
+    Manual(String),
+    Disabled,
+}
+
+impl ProxyMode {
+    pub fn agent(&self) -> Result<ureq::Agent, String> {
+        let builder = ureq::AgentBuilder::new();
+        let builder = match self {
+            ProxyMode::System => builder.try_proxy_from_env(true),
+            ProxyMode::Manual(url) => builder
+                .proxy(ureq::Proxy::new(url.trim()).map_err(|e| format!("Invalid proxy: {e}"))?),
+            ProxyMode::Disabled => builder,
+        };
+        Ok(builder.build())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct Endpoints {
+    pub login: String,
+    pub register: String,
+    pub me: String,
+    pub create_character: String,
+    pub delete_character: String,
+    pub reset_password: String,
+    /// GET, lists the client files with their sizes and hashes.
+    pub manifest: String,
+    /// Read timeout overrides in seconds, for endpoints that are slow on busy servers.
+    pub timeouts: HashMap<Endpoint, u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endpoint {
+    Login,
+    Register,
+    Me,
+    CreateCharacter,
+    DeleteCharacter,
+    ResetPassword,
+    Manifest,
+}
+
+impl Endpoint {
+    pub const ALL: [Endpoint; 7] = [
+        Endpoint::Login,
+        Endpoint::Register,
+        Endpoint::Me,
+        Endpoint::CreateCharacter,
+        Endpoint::DeleteCharacter,
+        Endpoint::ResetPassword,
+        Endpoint::Manifest,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Endpoint::Login => "Login",
+            Endpoint::Register => "Register",
+            Endpoint::Me => "Session",
+            Endpoint::CreateCharacter => "Create character",
+            Endpoint::DeleteCharacter => "Delete character",
+            Endpoint::ResetPassword => "Password reset",
+            Endpoint::Manifest => "File manifest",
+        }
+    }
+}
+
+impl Endpoints {
+    pub fn path(&self, endpoint: Endpoint) -> &str {
+        match endpoint {
+            Endpoint::Login => &self.login,
+            Endpoint::Register => &self.register,
+            Endpoint::Me => &self.me,
+            Endpoint::CreateCharacter => &self.create_character,
+            Endpoint::DeleteCharacter => &self.delete_character,
+            Endpoint::ResetPassword => &self.reset_password,
+            Endpoint::Manifest => &self.manifest,
+        }
+    }
+
+    pub fn path_mut(&mut self, endpoint: Endpoint) -> &mut String {
+        match endpoint {
+            Endpoint::Login => &mut self.login,
+            Endpoint::Register => &mut self.register,
+            Endpoint::Me => &mut self.me,
+            Endpoint::CreateCharacter => &mut self.create_character,
+            Endpoint::DeleteCharacter => &mut self.delete_character,
+            Endpoint::ResetPassword => &mut self.reset_password,
+            Endpoint::Manifest => &mut self.manifest,
+        }
+    }
+}
+
+impl Default for Endpoints {
+    fn default() -> Self {
+        Self {
+            login: "/login".into(),
+            register: "/register".into(),
+            me: "/me".into(),
+            create_character: "/character/create".into(),
+            delete_character: "/character/delete".into(),
+            reset_password: "/password/reset-request".into(),
+            manifest: "/launcher/manifest".into(),
+            timeouts: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SavedSession {
+    pub host: String,
+    pub username: String,
+    pub token: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            host: Host::default(),
+            custom_host: String::new(),
+            recent_hosts: Vec::new(),
+            window_pos: None,
+            window_size: None,
+            dev_mode: false,
+            mhf_folder: "F:/Games/Monster Hunter Frontier Online".into(),
+            game_workdir: String::new(),
+            input_limits: HashMap::new(),
+            stay_logged_in: false,
+            session: None,
+            character_views: HashMap::new(),
+            density: Density::default(),
+            themes: HashMap::new(),
+            accounts: HashMap::new(),
+            confirm_launch: false,
+            confirm_quit_while_running: true,
+            start_minimized: false,
+            password_retention: PasswordRetention::default(),
+            ui_scale: 1.0,
+            idle_logout_minutes: 0,
+            auto_refresh_minutes: 0,
+            gamepad: false,
+            proxy: ProxyMode::default(),
+            request_timeout_secs: 30,
+            max_response_mib: 8,
+            seen_motd: HashMap::new(),
+            mini_mode: false,
+            discord_presence: false,
+            discord_application_id: String::new(),
+            character_defaults: None,
+            last_run_version: None,
+            url_scheme_handler: None,
+            endpoints: Endpoints::default(),
+            password_reset: HashMap::new(),
+            field_casing: HashMap::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Where the config may live, in order of preference. Next to the
+    /// executable is for locked-down systems without a usable profile.
+    fn candidates() -> Vec<PathBuf> {
+        let mut candidates = Vec::new();
+        if let Some(dir) = dirs::config_dir() {
+            candidates.push(dir.join("mhf-launcher").join("config.json"));
+        }
+        if let Some(dir) = env::current_exe()
+            .ok()
+            .and_then(|exe| exe.parent().map(Path::to_owned))
+        {
+            candidates.push(dir.join("mhf-launcher.json"));
+        }
+        candidates
+    }
+
+    /// An existing config wins, otherwise the first location that can be written.
+    /// Probed once, every save and sibling file would touch the disk otherwise.
+    fn path() -> Option<PathBuf> {
+        static PATH: OnceLock<Option<PathBuf>> = OnceLock::new();
+        PATH.get_or_init(|| {
+            let candidates = Self::candidates();
+            candidates
+                .iter()
+                .find(|path| path.is_file())
+                .or_else(|| candidates.iter().find(|path| writable(path)))
+                .cloned()
+        })
+        .clone()
+    }
+
+    /// A file kept alongside the config, `None` when nothing can be saved.
+    pub fn sibling_path(file_name: &str) -> Option<PathBuf> {
+        Some(Self::path()?.with_file_name(file_name))
+    }
+
+    /// Whether settings survive a restart. Without any writable location the
+    /// launcher still works, it just keeps everything in memory.
+    pub fn is_persistent() -> bool {
+        Self::path().is_some()
+    }
+
+    pub fn load() -> Self {
+        let Some(path) = Self::path() else {
+            return Self::default();
+        };
+        match fs::read_to_string(path) {
+            Ok(text) => serde_json::from_str(&text).unwrap_or_else(|e| {
+                log::warn!("Failed to parse config, using defaults: {e}");
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Removes the saved config, a missing file is not an error.
+    pub fn delete() -> io::Result<()> {
+        for path in Self::candidates() {
+            match fs::remove_file(path) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// The JSON [`Config::save`] writes, to tell whether anything changed since.
+    pub fn to_json(&self) -> io::Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = Self::path() else {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "no writable location for the config",
+            ));
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, self.to_json()?)
+    }
+
+    /// Config as JSON with secrets replaced, suitable for sharing in bug reports.
+    pub fn redacted_json(&self) -> String {
+        let mut value = serde_json::to_value(self).unwrap_or_default();
+        if let Some(session) = value.get_mut("session").filter(|s| !s.is_null()) {
+            session["token"] = "<redacted>".into();
+        }
+        serde_json::to_string_pretty(&value).unwrap_or_default()
+    }
+
+    /// Portable copy of the config for moving to another PC. The saved
+    /// session is left out unless asked for, window geometry always is.
+    pub fn export_json(&self, include_session: bool) -> serde_json::Result<String> {
+        let mut value = serde_json::to_value(self)?;
+        for key in MACHINE_LOCAL {
+            value[key] = serde_json::Value::Null;
+        }
+        if !include_session {
+            value["session"] = serde_json::Value::Null;
+        }
+        serde_json::to_string_pretty(&value)
+    }
+
+    /// Applies an exported config on top of this one. Maps and lists are
+    /// combined, everything else set in the file replaces the current value.
+    pub fn merged_with(&self, json: &str) -> Result<Config, String> {
+        let mut imported: serde_json::Value =
+            serde_json::from_str(json).map_err(|e| format!("Not a valid settings file: {e}"))?;
+        let Some(object) = imported.as_object_mut() else {
+            return Err("Not a valid settings file: expected a JSON object".into());
+        };
+        for key in MACHINE_LOCAL {
+            object.remove(key);
+        }
+        // Decoding on its own first reports problems against the file rather than the merge.
+        serde_json::from_value::<Config>(imported.clone())
+            .map_err(|e| format!("Not a valid settings file: {e}"))?;
+        let mut merged = serde_json::to_value(self).map_err(|e| e.to_string())?;
+        merge_json(&mut merged, imported);
+        let mut merged: Config =
+            serde_json::from_value(merged).map_err(|e| format!("Unable to merge settings: {e}"))?;
+        // Both sides may be full, the union keeps the most recent of this PC's.
+        merged.recent_hosts.truncate(MAX_RECENT);
+        for accounts in merged.accounts.values_mut() {
+            accounts.truncate(MAX_RECENT);
+        }
+        Ok(merged)
+    }
+
+    pub fn push_recent_host(&mut self, host: &str) {
+        push_recent(&mut self.recent_hosts, host);
+    }
+
+    pub fn push_account(&mut self, host: &str, username: &str) {
+        push_recent(self.accounts.entry(host.to_owned()).or_default(), username);
+    }
+}
+
+fn merge_json(target: &mut serde_json::Value, source: serde_json::Value) {
+    use serde_json::Value;
+    match (target, source) {
+        (Value::Object(target), Value::Object(source)) => {
+            for (key, value) in source {
+                match target.get_mut(&key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => {
+                        target.insert(key, value);
+                    }
+                }
+            }
+        }
+        (Value::Array(target), Value::Array(source)) => {
+            for value in source {
+                if !target.contains(&value) {
+                    target.push(value);
+                }
+            }
+        }
+        // Machine-local settings and the session are exported as null, keep what this PC has.
+        (_, Value::Null) => {}
+        (target, source) => *target = source,
+    }
+}
+
+fn writable(path: &Path) -> bool {
+    let Some(dir) = path.parent() else {
+        return false;
+    };
+    let probe = dir.join(".mhf-launcher-probe");
+    let writable = fs::create_dir_all(dir)
+        .and_then(|()| fs::write(&probe, b""))
+        .is_ok();
+    fs::remove_file(&probe).ok();
+    writable
+}
+
+/// Moves `value` to the front of `list`, keeping at most [`MAX_RECENT`] unique entries.
+fn push_recent(list: &mut Vec<String>, value: &str) {
+    let value = value.trim();
+    if value.is_empty() {
+        return;
+    }
+    list.retain(|v| v != value);
+    list.insert(0, value.to_owned());
+    list.truncate(MAX_RECENT);
+}
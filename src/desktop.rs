@@ -0,0 +1,60 @@
+use std::{
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+/// Puts `text` on the system clipboard, reporting why when there is none
+/// (headless sessions, locked-down desktops, another app holding it open).
+pub fn copy_text(text: &str) -> Result<(), String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text))
+        .map_err(|e| e.to_string())
+}
+
+/// Opens `folder` in the system file explorer without waiting for it.
+pub fn open_folder(folder: &Path) -> Result<(), String> {
+    open::that_detached(folder).map_err(|e| e.to_string())
+}
+
+/// Shows a native folder picker. `Ok(None)` means the user cancelled.
+pub fn pick_folder() -> Result<Option<PathBuf>, String> {
+    guard(|| rfd::FileDialog::new().pick_folder())
+}
+
+/// Shows a native save dialog. `Ok(None)` means the user cancelled.
+pub fn save_file(
+    file_name: &str,
+    filter: &str,
+    extension: &str,
+) -> Result<Option<PathBuf>, String> {
+    guard(|| {
+        rfd::FileDialog::new()
+            .set_file_name(file_name)
+            .add_filter(filter, &[extension])
+            .save_file()
+    })
+}
+
+/// Shows a native open dialog. `Ok(None)` means the user cancelled.
+pub fn open_file(filter: &str, extension: &str) -> Result<Option<PathBuf>, String> {
+    guard(|| {
+        rfd::FileDialog::new()
+            .add_filter(filter, &[extension])
+            .pick_file()
+    })
+}
+
+/// Where files go when no save dialog can be shown.
+pub fn fallback_path(file_name: &str) -> Option<PathBuf> {
+    Some(dirs::config_dir()?.join("mhf-launcher").join(file_name))
+}
+
+// rfd panics instead of returning an error when the platform dialog can't be created.
+fn guard(dialog: impl FnOnce() -> Option<PathBuf>) -> Result<Option<PathBuf>, String> {
+    panic::catch_unwind(AssertUnwindSafe(dialog)).map_err(|payload| {
+        format!(
+            "File dialog unavailable: {}",
+            crate::panic_message(&*payload)
+        )
+    })
+}
@@ -0,0 +1,68 @@
+use std::{fs, path::Path, time::Duration};
+
+const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+const CONNECTIVITY_URL: &str = "http://www.msftconnecttest.com/connecttest.txt";
+
+pub struct Check {
+    pub name: &'static str,
+    pub result: Result<String, String>,
+    pub hint: &'static str,
+}
+
+pub fn run_all(agent: &ureq::Agent, mhf_folder: &str, host: &str) -> Vec<Check> {
+    vec![
+        Check {
+            name: "MHF folder",
+            result: crate::check_mhf_folder(mhf_folder).map(|_| mhf_folder.to_owned()),
+            hint: "Point the MHF folder in Settings at the directory containing mhf.exe",
+        },
+        Check {
+            name: "mhf.exe version",
+            result: exe_version(mhf_folder),
+            hint: "Reinstall the game client if mhf.exe is missing or damaged",
+        },
+        Check {
+            name: "Internet connection",
+            result: reachable(agent, CONNECTIVITY_URL),
+            hint: "Check your network cable/Wi-Fi and that no firewall blocks the launcher",
+        },
+        Check {
+            name: "Server",
+            result: reachable(agent, host),
+            hint: "Make sure the server is running and the host is correct, or ask the server operator",
+        },
+    ]
+}
+
+/// Any HTTP response, even an error status, means the host is reachable.
+pub fn reachable(agent: &ureq::Agent, url: &str) -> Result<String, String> {
+    match agent.get(url).timeout(CHECK_TIMEOUT).call() {
+        Ok(_) | Err(ureq::Error::Status(..)) => Ok(format!("{url} is reachable")),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Reads the `FileVersion` string out of the executable's version resource.
+fn exe_version(mhf_folder: &str) -> Result<String, String> {
+    let exe = Path::new(mhf_folder).join("mhf.exe");
+    let bytes = fs::read(&exe).map_err(|e| format!("Unable to read {}: {e}", exe.display()))?;
+    let key: Vec<u8> = "FileVersion\0"
+        .encode_utf16()
+        .flat_map(u16::to_le_bytes)
+        .collect();
+    let start = bytes
+        .windows(key.len())
+        .position(|w| w == key)
+        .ok_or("No version information found in mhf.exe")?
+        + key.len();
+    let version: Vec<u16> = bytes[start..]
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .skip_while(|&c| c == 0)
+        .take_while(|&c| c != 0)
+        .collect();
+    if version.is_empty() {
+        return Err("No version information found in mhf.exe".into());
+    }
+    Ok(String::from_utf16_lossy(&version))
+}
@@ -0,0 +1,39 @@
+use std::fs;
+
+use eframe::egui::{self, FontData, FontDefinitions, FontFamily};
+
+/// System fonts with Japanese glyphs, in order of preference. egui's bundled
+/// fonts cover Latin only, so names written in kana or kanji render as boxes
+/// without one of these.
+const CJK_FONTS: &[&str] = &[
+    "C:/Windows/Fonts/YuGothM.ttc",
+    "C:/Windows/Fonts/meiryo.ttc",
+    "C:/Windows/Fonts/msgothic.ttc",
+    "/System/Library/Fonts/ヒラギノ角ゴシック W3.ttc",
+    "/System/Library/Fonts/Hiragino Sans GB.ttc",
+    "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/google-noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/truetype/droid/DroidSansFallbackFull.ttf",
+];
+
+/// Adds the first CJK font found on the system as a fallback after egui's
+/// default fonts, so Latin text keeps its usual look.
+pub fn install_cjk_fallback(ctx: &egui::Context) {
+    let Some((path, bytes)) = CJK_FONTS
+        .iter()
+        .find_map(|path| fs::read(path).ok().map(|bytes| (path, bytes)))
+    else {
+        log::warn!("No CJK font found, Japanese character names may not render");
+        return;
+    };
+    let mut fonts = FontDefinitions::default();
+    fonts
+        .font_data
+        .insert("cjk".into(), FontData::from_owned(bytes));
+    for family in [FontFamily::Proportional, FontFamily::Monospace] {
+        fonts.families.entry(family).or_default().push("cjk".into());
+    }
+    ctx.set_fonts(fonts);
+    log::info!("Using {path} for CJK text");
+}
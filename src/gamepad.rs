@@ -0,0 +1,41 @@
+use gilrs::{Button, EventType, Gilrs};
+
+/// What a controller button press means to the launcher.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Action {
+    Up,
+    Down,
+    Confirm,
+    Back,
+}
+
+/// Polls connected controllers, mapping D-pad up/down, A and B to [`Action`]s.
+pub struct Gamepad {
+    gilrs: Gilrs,
+}
+
+impl Gamepad {
+    pub fn new() -> Result<Self, String> {
+        Gilrs::new()
+            .map(|gilrs| Self { gilrs })
+            .map_err(|e| e.to_string())
+    }
+
+    /// Drains pending controller events since the last call.
+    pub fn poll(&mut self) -> Vec<Action> {
+        let mut actions = Vec::new();
+        while let Some(event) = self.gilrs.next_event() {
+            let EventType::ButtonPressed(button, _) = event.event else {
+                continue;
+            };
+            actions.push(match button {
+                Button::DPadUp => Action::Up,
+                Button::DPadDown => Action::Down,
+                Button::South => Action::Confirm,
+                Button::East => Action::Back,
+                _ => continue,
+            });
+        }
+        actions
+    }
+}
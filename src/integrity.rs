@@ -0,0 +1,74 @@
+use std::{
+    fs, io,
+    path::{Component, Path},
+};
+
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+/// File list a server publishes for its client, paths relative to the MHF folder.
+#[derive(Deserialize)]
+pub struct Manifest {
+    pub files: Vec<ManifestFile>,
+}
+
+#[derive(Deserialize)]
+pub struct ManifestFile {
+    pub path: String,
+    pub size: u64,
+    /// Lowercase hex, files without one are only checked by size.
+    #[serde(default)]
+    pub sha256: Option<String>,
+}
+
+pub struct Report {
+    pub checked: usize,
+    pub problems: Vec<String>,
+}
+
+/// Compares the MHF folder against `manifest`. Hashing reads every listed
+/// file in full, so this belongs on a background thread.
+pub fn verify(mhf_folder: &str, manifest: &Manifest) -> Report {
+    let root = Path::new(mhf_folder);
+    let mut problems = Vec::new();
+    for file in &manifest.files {
+        let relative = Path::new(&file.path);
+        // A manifest only ever describes files inside the game folder.
+        if !relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+        {
+            problems.push(format!("{}: invalid path in manifest", file.path));
+            continue;
+        }
+        if let Err(problem) = check_file(&root.join(relative), file) {
+            problems.push(format!("{}: {problem}", file.path));
+        }
+    }
+    Report {
+        checked: manifest.files.len(),
+        problems,
+    }
+}
+
+fn check_file(path: &Path, file: &ManifestFile) -> Result<(), String> {
+    let size = match fs::metadata(path) {
+        Ok(metadata) => metadata.len(),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Err("missing".into()),
+        Err(e) => return Err(format!("unable to read ({e})")),
+    };
+    if size != file.size {
+        return Err(format!("expected {} bytes, found {size}", file.size));
+    }
+    let Some(expected) = &file.sha256 else {
+        return Ok(());
+    };
+    let mut hasher = Sha256::new();
+    fs::File::open(path)
+        .and_then(|mut f| io::copy(&mut f, &mut hasher))
+        .map_err(|e| format!("unable to read ({e})"))?;
+    if format!("{:x}", hasher.finalize()) != expected.to_lowercase() {
+        return Err("corrupt, contents don't match".into());
+    }
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use std::{fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// The most recent error of a run. Removed when the launcher closes normally,
+/// so finding one at startup means the previous run crashed or was killed.
+#[derive(Serialize, Deserialize)]
+pub struct LastError {
+    /// Unix timestamp.
+    pub at: i64,
+    pub message: String,
+}
+
+fn path() -> Option<PathBuf> {
+    crate::config::Config::sibling_path("mhf-launcher-last-error.json")
+}
+
+/// Best effort, a failure here must not turn into another error.
+pub fn record(message: &str) {
+    let Some(path) = path() else {
+        return;
+    };
+    let error = LastError {
+        at: crate::unix_now(),
+        message: message.to_owned(),
+    };
+    if let (Some(parent), Ok(json)) = (path.parent(), serde_json::to_string(&error)) {
+        let _ = fs::create_dir_all(parent).and_then(|_| fs::write(&path, json));
+    }
+}
+
+/// Reads and removes the error left by the previous run, if any.
+pub fn take() -> Option<LastError> {
+    let path = path()?;
+    let json = fs::read_to_string(&path).ok()?;
+    let _ = fs::remove_file(&path);
+    serde_json::from_str(&json).ok()
+}
+
+pub fn clear() {
+    if let Some(path) = path() {
+        let _ = fs::remove_file(path);
+    }
+}
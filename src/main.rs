@@ -1,59 +1,368 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod branding;
+mod certificate;
+mod config;
+mod deeplink;
+mod desktop;
+mod diagnostics;
+mod fonts;
+mod gamepad;
+mod integrity;
+mod last_error;
+mod logger;
+mod presence;
+mod schema;
+
+use std::{
+    any::Any,
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    env, fmt, fs,
+    io::{self, Read},
+    mem,
+    ops::RangeInclusive,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    process,
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use branding::Branding;
+use config::{
+    CharacterDefaults, Config, Endpoint, FieldCasing, PasswordReset, ProxyMode, SavedSession,
+    ServerTheme,
+};
 use eframe::egui;
 use mhf_iel::{MezFesStall, MhfConfig, Notification};
-use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use schema::{Field, Kind, Shape};
+use serde::{
+    de::{self, DeserializeOwned},
+    Deserialize, Deserializer, Serialize,
+};
+use serde_json::Value;
 use ureq::Response;
+use uuid::Uuid;
 
-#[derive(Deserialize, Default)]
+const TOAST_DURATION: Duration = Duration::from_secs(2);
+const HIGHLIGHT_DURATION: Duration = Duration::from_secs(2);
+const IDLE_WARNING: Duration = Duration::from_secs(30);
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(10);
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+const GAMEPAD_POLL_INTERVAL: Duration = Duration::from_millis(50);
+const MINI_WINDOW_SIZE: egui::Vec2 = egui::vec2(360.0, 110.0);
+const DEFAULT_WINDOW_SIZE: egui::Vec2 = egui::vec2(640.0, 480.0);
+const LAUNCHER_VERSION: &str = env!("CARGO_PKG_VERSION");
+const CHANGELOG: &str = include_str!("../CHANGELOG.md");
+const UI_SCALE_RANGE: RangeInclusive<f32> = 0.75..=2.0;
+const DEFAULT_RETRY_AFTER_SECS: u64 = 30;
+/// A longer wait is more likely a misconfigured server than a real limit.
+const MAX_RETRY_AFTER_SECS: u64 = 3600;
+const MAX_REQUEST_TIMEOUT_SECS: u64 = 600;
+/// Attempts for character creation when the connection drops before a reply.
+const CREATE_ATTEMPTS: usize = 3;
+/// Wait before the next creation attempt, multiplied by the attempts so far.
+const CREATE_RETRY_DELAY: Duration = Duration::from_millis(500);
+/// Slot count assumed when the server doesn't report one.
+const DEFAULT_CHAR_LIMIT: usize = 6;
+// Caps on what a misbehaving server can make the UI lay out. The full values
+// are still what the game receives.
+const MAX_NAME_CHARS: usize = 32;
+const MAX_NOTIFICATION_CHARS: usize = 2000;
+/// How many characters the "Recently played" strip shows at most.
+const RECENT_CHARACTERS: usize = 3;
+
+#[derive(Deserialize, Serialize, Default, Clone)]
 #[serde(rename_all = "camelCase")]
 struct User {
+    #[serde(default)]
     rights: u32,
     token: String,
 }
 
-#[derive(Deserialize, Default, Clone)]
+#[derive(Deserialize, Serialize, Default, Clone)]
 #[serde(rename_all = "camelCase")]
 struct Character {
     id: u32,
     name: String,
     #[serde(default)]
     is_new: bool,
+    #[serde(default)]
     is_female: bool,
+    #[serde(default, deserialize_with = "lenient_u32")]
     weapon: u32,
+    #[serde(default, deserialize_with = "lenient_u32")]
     hr: u32,
+    #[serde(default, deserialize_with = "lenient_u32")]
     gr: u32,
+    #[serde(default)]
     last_login: i64,
 }
 
-#[derive(Deserialize)]
+/// Accepts `12` as well as `"12"`, some servers send every number as a string.
+fn lenient_u32<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum NumberOrString {
+        Number(u32),
+        String(String),
+    }
+    match NumberOrString::deserialize(deserializer)? {
+        NumberOrString::Number(n) => Ok(n),
+        NumberOrString::String(s) => s
+            .trim()
+            .parse()
+            .map_err(|_| de::Error::invalid_value(de::Unexpected::Str(&s), &"an unsigned integer")),
+    }
+}
+
+impl Character {
+    fn display_name(&self) -> Cow<'_, str> {
+        truncate_chars(&self.name, MAX_NAME_CHARS)
+    }
+
+    /// Plain-text summary for support requests. Contains nothing secret.
+    fn details(&self, server_now: i64) -> String {
+        let last_login = if self.last_login > 0 {
+            format!(
+                "{} ({} ago)",
+                self.last_login,
+                format_duration(server_now - self.last_login)
+            )
+        } else {
+            "never".into()
+        };
+        format!(
+            "ID: {}\nName: {}\nHR: {}\nGR: {}\nWeapon: {}\nGender: {}\nLast login: {last_login}",
+            self.id,
+            self.name,
+            self.hr,
+            self.gr,
+            self.weapon,
+            if self.is_female { "Female" } else { "Male" },
+        )
+    }
+}
+
+/// Weapon names by the id the game uses.
+const WEAPONS: [&str; 14] = [
+    "Great Sword",
+    "Heavy Bowgun",
+    "Hammer",
+    "Lance",
+    "Sword and Shield",
+    "Light Bowgun",
+    "Dual Swords",
+    "Long Sword",
+    "Hunting Horn",
+    "Gunlance",
+    "Bow",
+    "Tonfa",
+    "Switch Axe F",
+    "Magnet Spike",
+];
+
+/// What changed in the character list between two refreshes.
+struct CharacterChanges {
+    added: Vec<u32>,
+    changed: Vec<u32>,
+    removed: Vec<String>,
+    at: Instant,
+}
+
+impl CharacterChanges {
+    fn between(previous: &[Character], current: &[Character]) -> Self {
+        let mut added = Vec::new();
+        let mut changed = Vec::new();
+        for character in current {
+            match previous.iter().find(|c| c.id == character.id) {
+                None => added.push(character.id),
+                Some(old)
+                    if old.hr != character.hr
+                        || old.gr != character.gr
+                        || old.last_login != character.last_login =>
+                {
+                    changed.push(character.id)
+                }
+                Some(_) => {}
+            }
+        }
+        let removed = previous
+            .iter()
+            .filter(|c| !current.iter().any(|n| n.id == c.id))
+            .map(|c| c.display_name().into_owned())
+            .collect();
+        Self {
+            added,
+            changed,
+            removed,
+            at: Instant::now(),
+        }
+    }
+
+    /// Highlight strength, fading from 1 to 0 over [`HIGHLIGHT_DURATION`].
+    fn fade(&self) -> f32 {
+        1.0 - (self.at.elapsed().as_secs_f32() / HIGHLIGHT_DURATION.as_secs_f32()).min(1.0)
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 struct MezFes {
     id: u32,
     start: u32,
     end: u32,
+    #[serde(default)]
     solo_tickets: u32,
+    #[serde(default)]
     group_tickets: u32,
+    #[serde(default)]
     stalls: Vec<u32>,
 }
 
-#[derive(Deserialize, Default)]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum NotificationRepr {
+    Text(String),
+    Full {
+        data: String,
+        #[serde(default)]
+        flags: u32,
+    },
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(from = "NotificationRepr")]
+struct NotificationData {
+    data: String,
+    flags: u32,
+}
+
+impl From<NotificationRepr> for NotificationData {
+    fn from(repr: NotificationRepr) -> Self {
+        match repr {
+            NotificationRepr::Text(data) => Self { data, flags: 0 },
+            NotificationRepr::Full { data, flags } => Self { data, flags },
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Default, Clone)]
 #[serde(rename_all = "camelCase")]
 struct AuthData {
     current_ts: u32,
+    #[serde(default)]
     expiry_ts: u32,
+    #[serde(default)]
     entrance_count: u32,
-    notifications: Vec<String>,
+    #[serde(default)]
+    notifications: Vec<NotificationData>,
     user: User,
+    #[serde(default)]
     characters: Vec<Character>,
     mez_fes: Option<MezFes>,
+    branding: Option<Branding>,
+    limits: Option<InputLimits>,
+    #[serde(default)]
+    char_limit: Option<usize>,
+    /// Message of the day, shown once per host until it changes.
+    #[serde(default)]
+    motd: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy)]
+#[serde(rename_all = "camelCase", default)]
+struct InputLimits {
+    username_max: usize,
+    password_max: usize,
+}
+
+impl Default for InputLimits {
+    fn default() -> Self {
+        Self {
+            username_max: 32,
+            password_max: 32,
+        }
+    }
 }
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct Empty {}
 
-#[derive(Default, PartialEq)]
+impl Shape for Empty {
+    const SHAPE: Kind = Kind::Any;
+}
+
+impl Shape for Character {
+    const SHAPE: Kind = Kind::Object(&[
+        Field::required("id", Kind::Unsigned),
+        Field::required("name", Kind::String),
+        Field::optional("isNew", Kind::Bool),
+        Field::optional("isFemale", Kind::Bool),
+        Field::optional("weapon", Kind::Either(&Kind::Unsigned, &Kind::String)),
+        Field::optional("hr", Kind::Either(&Kind::Unsigned, &Kind::String)),
+        Field::optional("gr", Kind::Either(&Kind::Unsigned, &Kind::String)),
+        Field::optional("lastLogin", Kind::Integer),
+    ]);
+}
+
+impl Shape for AuthData {
+    const SHAPE: Kind = Kind::Object(&[
+        Field::required("currentTs", Kind::Unsigned),
+        Field::optional("expiryTs", Kind::Unsigned),
+        Field::optional("entranceCount", Kind::Unsigned),
+        Field::optional(
+            "notifications",
+            Kind::Array(&Kind::Either(
+                &Kind::String,
+                &Kind::Object(&[
+                    Field::required("data", Kind::String),
+                    Field::optional("flags", Kind::Unsigned),
+                ]),
+            )),
+        ),
+        Field::required(
+            "user",
+            Kind::Object(&[
+                Field::optional("rights", Kind::Unsigned),
+                Field::required("token", Kind::String),
+            ]),
+        ),
+        Field::optional("characters", Kind::Array(&Character::SHAPE)),
+        Field::optional(
+            "mezFes",
+            Kind::Object(&[
+                Field::required("id", Kind::Unsigned),
+                Field::required("start", Kind::Unsigned),
+                Field::required("end", Kind::Unsigned),
+                Field::optional("soloTickets", Kind::Unsigned),
+                Field::optional("groupTickets", Kind::Unsigned),
+                Field::optional("stalls", Kind::Array(&Kind::Unsigned)),
+            ]),
+        ),
+        Field::optional(
+            "branding",
+            Kind::Object(&[
+                Field::optional("accentColor", Kind::String),
+                Field::optional("bannerUrl", Kind::String),
+            ]),
+        ),
+        Field::optional(
+            "limits",
+            Kind::Object(&[
+                Field::optional("usernameMax", Kind::Unsigned),
+                Field::optional("passwordMax", Kind::Unsigned),
+            ]),
+        ),
+        Field::optional("charLimit", Kind::Unsigned),
+        Field::optional("motd", Kind::String),
+    ]);
+}
+
+#[derive(Default, PartialEq, Clone, Copy, Serialize, Deserialize)]
 enum Host {
     #[default]
     LocalHost,
@@ -69,9 +378,160 @@ impl Host {
     }
 }
 
+#[derive(Default, PartialEq, Clone, Copy)]
+enum ServerStatus {
+    #[default]
+    Unknown,
+    Online,
+    Offline,
+}
+
+impl ServerStatus {
+    fn label(&self) -> &str {
+        match self {
+            ServerStatus::Unknown => "Not connected",
+            ServerStatus::Online => "Online",
+            ServerStatus::Offline => "Offline",
+        }
+    }
+
+    fn color(&self) -> egui::Color32 {
+        match self {
+            ServerStatus::Unknown => egui::Color32::GRAY,
+            ServerStatus::Online => egui::Color32::GREEN,
+            ServerStatus::Offline => egui::Color32::RED,
+        }
+    }
+}
+
+/// How long the typed password is kept in memory. It is never written to disk.
+#[derive(Default, PartialEq, Clone, Copy, Serialize, Deserialize)]
+enum PasswordRetention {
+    /// Keep it until logout so re-authenticating doesn't need it retyped.
+    #[default]
+    Session,
+    /// Clear it as soon as the game has it, the next launch asks again.
+    Never,
+}
+
+impl PasswordRetention {
+    fn label(&self) -> &str {
+        match self {
+            PasswordRetention::Session => "Keep for this session",
+            PasswordRetention::Never => "Clear after launch",
+        }
+    }
+}
+
+#[derive(Default, PartialEq, Clone, Copy, Serialize, Deserialize)]
+enum Density {
+    Compact,
+    #[default]
+    Comfortable,
+}
+
+impl Density {
+    fn label(&self) -> &str {
+        match self {
+            Density::Compact => "Compact",
+            Density::Comfortable => "Comfortable",
+        }
+    }
+
+    fn font_size(&self) -> f32 {
+        match self {
+            Density::Compact => 16.0,
+            Density::Comfortable => 24.0,
+        }
+    }
+
+    fn apply_spacing(&self, spacing: &mut egui::style::Spacing) {
+        *spacing = egui::style::Spacing::default();
+        if *self == Density::Compact {
+            spacing.item_spacing = egui::vec2(6.0, 2.0);
+            spacing.button_padding = egui::vec2(3.0, 0.0);
+            spacing.interact_size.y = 14.0;
+        }
+    }
+}
+
+#[derive(Default, PartialEq, Clone, Copy, Serialize, Deserialize)]
+enum SortKey {
+    #[default]
+    Default,
+    Name,
+    Hr,
+    Gr,
+    LastLogin,
+}
+
+impl SortKey {
+    const ALL: [SortKey; 5] = [
+        SortKey::Default,
+        SortKey::Name,
+        SortKey::Hr,
+        SortKey::Gr,
+        SortKey::LastLogin,
+    ];
+
+    fn label(&self) -> &str {
+        match self {
+            SortKey::Default => "Default",
+            SortKey::Name => "Name",
+            SortKey::Hr => "HR",
+            SortKey::Gr => "GR",
+            SortKey::LastLogin => "Last Login",
+        }
+    }
+}
+
+#[derive(Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+struct CharacterView {
+    sort_key: SortKey,
+    descending: bool,
+    filter: String,
+    /// Character ids that always sort above the rest.
+    pinned: Vec<u32>,
+    /// Private notes by character id, never sent to the server.
+    notes: HashMap<u32, String>,
+    launches: HashMap<u32, LaunchRecord>,
+}
+
+/// Outcome of the most recent launch of a character.
+#[derive(Clone, Serialize, Deserialize)]
+struct LaunchRecord {
+    /// Local unix time the launch finished.
+    at: i64,
+    error: Option<String>,
+}
+
+impl CharacterView {
+    fn apply<'a>(&self, characters: &'a [Character]) -> Vec<&'a Character> {
+        let filter = self.filter.to_lowercase();
+        let mut visible: Vec<&Character> = characters
+            .iter()
+            .filter(|c| filter.is_empty() || c.name.to_lowercase().contains(&filter))
+            .collect();
+        match self.sort_key {
+            SortKey::Default => {}
+            SortKey::Name => visible.sort_by_key(|c| c.name.to_lowercase()),
+            SortKey::Hr => visible.sort_by_key(|c| c.hr),
+            SortKey::Gr => visible.sort_by_key(|c| c.gr),
+            SortKey::LastLogin => visible.sort_by_key(|c| c.last_login),
+        }
+        if self.descending {
+            visible.reverse();
+        }
+        visible.sort_by_key(|c| !self.pinned.contains(&c.id));
+        visible
+    }
+}
+
 enum CharacterOp {
     Start,
     Delete,
+    CopyInfo,
 }
 
 #[derive(Serialize)]
@@ -81,10 +541,36 @@ struct UserRequest<'a> {
     username: &'a str,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionRequest<'a> {
+    token: &'a str,
+}
+
+/// Body of a `426 Upgrade Required` response. Every field is optional so
+/// servers can send as little as the bare status.
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct UpdateRequired {
+    min_version: Option<String>,
+    download_url: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ResetRequest<'a> {
+    username: &'a str,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 struct CreateCharRequest<'a> {
     token: &'a str,
+    idempotency_key: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    is_female: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    weapon: Option<u32>,
 }
 
 #[derive(Serialize)]
@@ -94,112 +580,27 @@ struct DeleteCharRequest<'a> {
     char_id: u32,
 }
 
-#[derive(Default)]
+#[derive(Default, Clone, Copy)]
 enum MhfState {
     #[default]
     Login,
     Character,
+    Diagnostics,
+    /// Waiting for the server to answer a login or registration.
+    Connecting,
+    /// The login or registration request never reached the server.
+    Unreachable,
 }
 
-#[derive(Default)]
-struct MhfLauncher {
-    state: MhfState,
+struct LaunchData {
+    auth_data: AuthData,
     username: String,
     password: String,
-    custom_host: String,
-    auth_data: AuthData,
-    error_message: Option<String>,
-    host: Host,
+    mhf_folder: String,
 }
 
-impl MhfLauncher {
-    fn get_host(&self) -> &str {
-        match self.host {
-            Host::LocalHost => "http://127.0.0.1:8080",
-            Host::Custom => &self.custom_host,
-        }
-    }
-
-    fn handle_resposne<T: DeserializeOwned>(
-        &mut self,
-        response: Result<Response, ureq::Error>,
-    ) -> Option<T> {
-        match response {
-            Ok(r) => {
-                match r.into_json() {
-                    Ok(data) => {
-                        self.error_message = None;
-                        return Some(data);
-                    }
-                    Err(e) => {
-                        self.error_message = Some(format!("Failed to decode JSON response: {e}"))
-                    }
-                };
-            }
-            Err(ureq::Error::Status(_, r)) => {
-                let mut text = r.into_string().unwrap();
-                if text.is_empty() {
-                    text = "Unable to connect to server, try again later".into();
-                }
-                self.error_message = Some(text)
-            }
-            Err(_) => self.error_message = Some("Failed to connect to server".to_owned()),
-        };
-        None
-    }
-
-    fn request_login(&mut self) {
-        let result = self.handle_resposne(
-            ureq::post(&format!("{}/login", self.get_host())).send_json(UserRequest {
-                username: &self.username,
-                password: &self.password,
-            }),
-        );
-        if let Some(auth_data) = result {
-            self.auth_data = auth_data;
-        }
-    }
-
-    fn request_register(&mut self) {
-        let result = self.handle_resposne(
-            ureq::post(&format!("{}/register", self.get_host())).send_json(UserRequest {
-                username: &self.username,
-                password: &self.password,
-            }),
-        );
-        if let Some(auth_data) = result {
-            self.auth_data = auth_data;
-        }
-    }
-
-    fn request_create_character(&mut self) {
-        let result: Option<Character> = self.handle_resposne(
-            ureq::post(&format!("{}/character/create", self.get_host())).send_json(
-                CreateCharRequest {
-                    token: &self.auth_data.user.token,
-                },
-            ),
-        );
-        if let Some(character) = result {
-            self.handle_start(character);
-        };
-    }
-
-    fn request_delete_character(&mut self, character: Character) {
-        let result: Option<Empty> = self.handle_resposne(
-            ureq::post(&format!("{}/character/delete", self.get_host())).send_json(
-                DeleteCharRequest {
-                    token: &self.auth_data.user.token,
-                    char_id: character.id,
-                },
-            ),
-        );
-        if let Some(Empty) = result {
-            self.auth_data.characters.retain(|c| c.id != character.id);
-        };
-    }
-
-    fn handle_start(&mut self, character: Character) {
+impl LaunchData {
+    fn build_config<'a>(&'a self, character: &'a Character) -> MhfConfig<'a> {
         let auth_data = &self.auth_data;
         let char_ids: Vec<u32> = auth_data.characters.iter().map(|c| c.id).collect();
         let mut config = MhfConfig {
@@ -209,7 +610,10 @@ impl MhfLauncher {
             notifications: auth_data
                 .notifications
                 .iter()
-                .map(|n| Notification { data: n, flags: 0 })
+                .map(|n| Notification {
+                    data: &n.data,
+                    flags: n.flags as _,
+                })
                 .collect(),
             char_id: character.id,
             char_new: character.is_new,
@@ -232,122 +636,4550 @@ impl MhfLauncher {
             config.mez_stalls = mez_fes
                 .stalls
                 .iter()
-                .map(|v| <u32 as TryInto<MezFesStall>>::try_into(*v).unwrap())
+                .filter_map(|&v| {
+                    let stall = MezFesStall::try_from(v).ok();
+                    if stall.is_none() {
+                        log::warn!("Skipping unknown MezFes stall {v}");
+                    }
+                    stall
+                })
                 .collect();
         }
-        config.mhf_folder = Some("F:/Games/Monster Hunter Frontier Online".into());
-        mhf_iel::run(config).unwrap();
+        config.mhf_folder = Some(self.mhf_folder.as_str().into());
+        config
     }
+}
 
-    fn render_login(&mut self, ctx: &egui::Context) {
-        egui::CentralPanel::default().show(ctx, |ui| {
-            ui.heading("Sample MHF Launcher");
-            ui.text_edit_singleline(&mut self.username)
-                .labelled_by(ui.label("Username").id);
-            ui.text_edit_singleline(&mut self.password)
-                .labelled_by(ui.label("Password").id);
-            ui.separator();
-
-            egui::ComboBox::from_label("Host")
-                .selected_text(self.host.label())
-                .show_ui(ui, |ui| {
-                    ui.selectable_value(&mut self.host, Host::LocalHost, Host::LocalHost.label());
-                    ui.selectable_value(&mut self.host, Host::Custom, Host::Custom.label());
-                });
-            if self.host == Host::Custom {
-                ui.text_edit_singleline(&mut self.custom_host)
-                    .labelled_by(ui.label("Custom Host").id);
-            }
-            ui.separator();
-            ui.horizontal(|ui| {
-                if ui.button("Login").clicked() {
-                    self.request_login();
-                    self.state = MhfState::Character;
-                }
-                if ui.button("Register").clicked() {
-                    self.request_register();
-                    self.state = MhfState::Character;
-                }
-            });
-            if let Some(error_message) = &self.error_message {
-                ui.label(error_message);
-            }
-        });
+/// Renders the values handed to `mhf_iel::run` for the dev panel's dry run.
+fn describe_config(config: &MhfConfig, show_secrets: bool) -> String {
+    let secret = |value: &str| {
+        if show_secrets {
+            value.to_owned()
+        } else {
+            "<redacted>".into()
+        }
+    };
+    let mut lines = vec![
+        format!("char_id: {}", config.char_id),
+        format!("char_name: {}", config.char_name),
+        format!("char_new: {}", config.char_new),
+        format!("char_hr: {}", config.char_hr),
+        format!("char_gr: {}", config.char_gr),
+        format!("char_ids: {:?}", config.char_ids),
+        format!("user_name: {}", config.user_name),
+        format!("user_password: {}", secret(config.user_password)),
+        format!("user_token: {}", secret(config.user_token)),
+        format!("user_rights: {}", config.user_rights),
+        format!("entrance_count: {}", config.entrance_count),
+        format!("current_ts: {}", config.current_ts),
+        format!("expiry_ts: {}", config.expiry_ts),
+        format!("mez_event_id: {}", config.mez_event_id),
+        format!("mez_start: {}", config.mez_start),
+        format!("mez_end: {}", config.mez_end),
+        format!("mez_solo_tickets: {}", config.mez_solo_tickets),
+        format!("mez_group_tickets: {}", config.mez_group_tickets),
+        format!("mez_stalls: {} stall(s)", config.mez_stalls.len()),
+        format!(
+            "mhf_folder: {}",
+            config
+                .mhf_folder
+                .as_deref()
+                .map_or("<none>".into(), |p| p.display().to_string())
+        ),
+        format!("notifications: {}", config.notifications.len()),
+    ];
+    for notification in &config.notifications {
+        lines.push(format!(
+            "  [flags {}] {}",
+            notification.flags, notification.data
+        ));
     }
+    lines.join("\n")
+}
 
-    fn render_characters(&mut self, ctx: &egui::Context) {
-        egui::CentralPanel::default().show(ctx, |ui| {
-            let mut selected = None;
-            for character in self.auth_data.characters.iter() {
-                ui.horizontal(|ui| {
-                    ui.label("ID:");
-                    ui.label(&character.id.to_string());
-                    ui.label("Name:");
-                    ui.label(&character.name);
-                    ui.separator();
-                    ui.label("HR");
-                    ui.label(&character.hr.to_string());
-                    ui.separator();
-                    ui.label("GR");
-                    ui.label(&character.gr.to_string());
-                    if ui.button("Start").clicked() {
-                        selected = Some((character.clone(), CharacterOp::Start));
-                    }
-                    if ui.button("Deleted").clicked() {
-                        selected = Some((character.clone(), CharacterOp::Delete));
-                    }
-                });
-                ui.separator();
-            }
-            if let Some((character, op)) = selected {
-                match op {
-                    CharacterOp::Start => self.handle_start(character),
-                    CharacterOp::Delete => self.request_delete_character(character),
-                };
-            }
-            ui.horizontal(|ui| {
-                if ui.button("Create").clicked() {
-                    self.request_create_character();
-                }
-                if ui.button("Logout").clicked() {
-                    self.error_message = None;
-                    self.state = MhfState::Login;
-                }
-            });
-            if let Some(error_message) = &self.error_message {
-                ui.label(error_message);
-            }
-        });
-    }
+enum LaunchError {
+    /// The MHF folder or mhf.exe disappeared before the game could be started.
+    NotFound(String),
+    /// mhf_iel panicked while starting, usually due to a missing runtime component.
+    MissingRuntime(String),
+    /// The game was started but mhf_iel reported an error.
+    Failed(String),
+    /// The launcher itself panicked while preparing the game.
+    Internal(String),
 }
 
-impl eframe::App for MhfLauncher {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        ctx.style_mut(|style| {
-            for (_, font_id) in style.text_styles.iter_mut() {
-                font_id.size = 24.0;
-            }
-        });
-        match self.state {
-            MhfState::Login => self.render_login(ctx),
-            MhfState::Character => self.render_characters(ctx),
+impl fmt::Display for LaunchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LaunchError::NotFound(e) => write!(f, "The game could not be found: {e}"),
+            LaunchError::MissingRuntime(e) => write!(
+                f,
+                "The game could not be started (missing runtime component): {e}"
+            ),
+            LaunchError::Failed(e) => write!(f, "The game failed after starting: {e}"),
+            LaunchError::Internal(e) => write!(f, "The launcher failed to prepare the game: {e}"),
         }
     }
 }
 
-fn main() -> Result<(), eframe::Error> {
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown error".into()
+    }
+}
+
+/// Cache validators from the last session response, sent back on refresh.
+#[derive(Default)]
+struct SessionValidators {
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum SignInKind {
+    Login,
+    Register,
+    /// Checking the session saved by "Stay logged in".
+    Restore,
+    /// A session token pasted on the login screen.
+    Token,
+}
+
+/// A login, registration or session check waiting for the server's answer.
+struct SignIn {
+    kind: SignInKind,
+    response: mpsc::Receiver<Result<Response, ureq::Error>>,
+    /// The token a session check was sent with, `None` for password sign-ins.
+    token: Option<String>,
+}
+
+#[derive(Clone, Copy)]
+enum RefreshKind {
+    /// The Refresh button, failures open the error dialog.
+    Manual,
+    Auto,
+    /// The server answered again after being unreachable.
+    Reconnect,
+}
+
+/// A session refresh waiting for the server's answer.
+struct Refresh {
+    kind: RefreshKind,
+    /// Characters before the refresh, to highlight what changed.
+    previous: Vec<Character>,
+    response: mpsc::Receiver<Result<Response, ureq::Error>>,
+}
+
+/// What the password prompt unlocks once the server accepts the password.
+enum ReauthAction {
+    /// Only asked while a saved session stands in for the login.
+    Delete(Character),
+    /// The game needs the password and there is none to hand it.
+    Start(Character),
+}
+
+struct Reauth {
+    action: ReauthAction,
+    password: String,
+    /// The login checking the password, once submitted.
+    response: Option<mpsc::Receiver<Result<Response, ureq::Error>>>,
+}
+
+/// What the error dialog's Retry button does again.
+enum Retry {
+    Start(u32),
+    Refresh,
+}
+
+/// A failure worth interrupting for, shown in a modal instead of the inline label.
+struct ErrorDialog {
+    title: &'static str,
+    message: String,
+    retry: Option<Retry>,
+}
+
+struct Launch {
+    char_id: u32,
+    account: String,
+    character_name: String,
+    result: mpsc::Receiver<Result<(), LaunchError>>,
+    /// The user stopped waiting, the game still runs and blocks other launches.
+    detached: bool,
+}
+
+/// Everything tied to one server connection. Each tab of the launcher has its
+/// own, the config and the window are shared.
+#[derive(Default)]
+struct Tab {
+    state: MhfState,
+    username: String,
+    password: String,
+    custom_host: String,
+    auth_data: AuthData,
+    error_message: Option<String>,
+    error_dialog: Option<ErrorDialog>,
+    host: Host,
+    launch: Option<Launch>,
+    banner: Option<egui::TextureHandle>,
+    banner_request: Option<mpsc::Receiver<Result<egui::ColorImage, String>>>,
+    reauth: Option<Reauth>,
+    diagnostics: Vec<diagnostics::Check>,
+    diagnostics_request: Option<mpsc::Receiver<Vec<diagnostics::Check>>>,
+    diagnostics_return: MhfState,
+    server_offset: i64,
+    rate_limited_until: Option<Instant>,
+    token_input: String,
+    quick_connect: String,
+    pending_start: Option<Character>,
+    launch_failed: bool,
+    server_status: ServerStatus,
+    connection_check: Option<mpsc::Receiver<bool>>,
+    /// Only for this run, the warning is back on the next launch.
+    insecure_banner_dismissed: bool,
+    session_validators: SessionValidators,
+    sign_in: Option<SignIn>,
+    refresh: Option<Refresh>,
+    /// Announcements shown in full despite their length, by index.
+    expanded_notifications: HashSet<usize>,
+    last_refreshed: Option<Instant>,
+    last_auto_refresh: Option<Instant>,
+    /// Transport error of the last request that never got a response.
+    connect_error: Option<String>,
+    retry_register: bool,
+    next_connection_check: Option<Instant>,
+    last_request_id: Option<String>,
+    character_changes: Option<CharacterChanges>,
+    update_required: Option<UpdateRequired>,
+    last_status: Option<u16>,
+    focus_username: bool,
+    /// Whether the last frame showed the login screen, to place focus when it appears.
+    login_shown: bool,
+    editing_note: Option<(u32, String)>,
+    certificate: Option<Result<certificate::CertificateInfo, String>>,
+    certificate_request: Option<mpsc::Receiver<Result<certificate::CertificateInfo, String>>>,
+    show_certificate: bool,
+    create_key: Option<String>,
+    create_request: Option<mpsc::Receiver<Result<Response, ureq::Error>>>,
+    pending_motd: Option<String>,
+    focus_note: bool,
+    confirm_force_close: bool,
+}
+
+#[derive(Default)]
+struct MhfLauncher {
+    /// The selected tab, swapped out of `tabs` while it's selected.
+    tab: Tab,
+    /// All tabs in strip order, the selected one's slot holds a placeholder.
+    tabs: Vec<Tab>,
+    selected: usize,
+    previous_error: Option<last_error::LastError>,
+    config: Config,
+    /// The config as last loaded or saved, it's only written when it changed.
+    saved_config: Option<String>,
+    show_settings: bool,
+    export_session: bool,
+    export_secrets: bool,
+    dry_run: bool,
+    dry_run_output: Option<String>,
+    folder_error: Option<String>,
+    base_visuals: egui::Visuals,
+    toast: Option<(String, Instant)>,
+    verify_request: Option<mpsc::Receiver<Result<integrity::Report, String>>>,
+    verify_result: Option<Result<integrity::Report, String>>,
+    whats_new: Option<&'static str>,
+    presence: Option<presence::Presence>,
+    confirm_quit: bool,
+    quit_confirmed: bool,
+    quit_dont_ask: bool,
+    minimize_on_start: bool,
+    last_input: Option<Instant>,
+    gamepad: Option<gamepad::Gamepad>,
+    gamepad_actions: Vec<gamepad::Action>,
+    gamepad_selection: usize,
+    confirm_reset: bool,
+    resize_window: bool,
+    show_reset_dialog: bool,
+    reset_username: String,
+}
+
+enum Markup<'a> {
+    Text(&'a str),
+    Bold(&'a str),
+    Link { text: &'a str, url: &'a str },
+}
+
+/// Recognizes a `**bold**` span, a `[text](url)` link or a bare http(s) URL at
+/// the start of `rest`, returning it with its length in bytes.
+fn markup_at(rest: &str) -> Option<(Markup<'_>, usize)> {
+    if let Some(bold) = rest.strip_prefix("**") {
+        let end = bold.find("**").filter(|&end| end > 0)?;
+        Some((Markup::Bold(&bold[..end]), end + 4))
+    } else if let Some(link) = rest.strip_prefix('[') {
+        let (text, tail) = link.split_once("](")?;
+        let url = &tail[..tail.find(')')?];
+        (!text.contains(']')).then(|| (Markup::Link { text, url }, text.len() + url.len() + 4))
+    } else if rest.starts_with("http://") || rest.starts_with("https://") {
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let url = &rest[..end];
+        Some((Markup::Link { text: url, url }, end))
+    } else {
+        None
+    }
+}
+
+/// Cuts `text` to `max` characters, marking the cut with an ellipsis.
+fn truncate_chars(text: &str, max: usize) -> Cow<'_, str> {
+    match text.char_indices().nth(max) {
+        Some((end, _)) => Cow::Owned(format!("{}…", &text[..end])),
+        None => Cow::Borrowed(text),
+    }
+}
+
+/// The changelog section for `version`, without its heading.
+fn release_notes(version: &str) -> Option<&'static str> {
+    CHANGELOG.split("\n## ").skip(1).find_map(|section| {
+        let (heading, notes) = section.split_once('\n')?;
+        (heading.trim().trim_start_matches('v') == version).then(|| notes.trim())
+    })
+}
+
+/// Splits an announcement line into plain text, bold spans and links.
+fn parse_markup(line: &str) -> Vec<Markup<'_>> {
+    let mut parts = Vec::new();
+    let mut text_start = 0;
+    let mut i = 0;
+    while let Some(c) = line[i..].chars().next() {
+        let Some((part, len)) = markup_at(&line[i..]) else {
+            i += c.len_utf8();
+            continue;
+        };
+        if text_start < i {
+            parts.push(Markup::Text(&line[text_start..i]));
+        }
+        parts.push(part);
+        i += len;
+        text_start = i;
+    }
+    if text_start < line.len() {
+        parts.push(Markup::Text(&line[text_start..]));
+    }
+    parts
+}
+
+fn render_markup(ui: &mut egui::Ui, line: &str, strong: bool) {
+    ui.horizontal_wrapped(|ui| {
+        ui.spacing_mut().item_spacing.x = 0.0;
+        if line.trim().is_empty() {
+            ui.label(" ");
+        }
+        for part in parse_markup(line) {
+            match part {
+                Markup::Text(text) if strong => {
+                    ui.label(egui::RichText::new(text).strong());
+                }
+                Markup::Text(text) => {
+                    ui.label(text);
+                }
+                Markup::Bold(text) => {
+                    ui.label(egui::RichText::new(text).strong());
+                }
+                Markup::Link { text, url } => {
+                    ui.hyperlink_to(text, url);
+                }
+            }
+        }
+    });
+}
+
+/// `{ "data": ..., "error": ... }` wrapper some server frameworks put around
+/// every response.
+struct Envelope<'a> {
+    data: &'a Value,
+    error: Option<String>,
+}
+
+impl<'a> Envelope<'a> {
+    /// Only objects whose keys are limited to `data` and `error` count, so a
+    /// bare response is never mistaken for an envelope.
+    fn parse(value: &'a Value) -> Option<Self> {
+        let map = value.as_object()?;
+        if map.is_empty() || map.keys().any(|k| k != "data" && k != "error") {
+            return None;
+        }
+        let error = match map.get("error") {
+            None | Some(Value::Null) => None,
+            Some(Value::String(error)) => Some(error.clone()),
+            Some(error) => Some(
+                error["message"]
+                    .as_str()
+                    .map_or_else(|| error.to_string(), str::to_owned),
+            ),
+        };
+        Some(Self {
+            data: map.get("data").unwrap_or(&Value::Null),
+            error,
+        })
+    }
+}
+
+/// `snake_case` to `camelCase`. Leading underscores are kept, runs of
+/// underscores count as one and a trailing run is kept as a single `_`.
+fn camel_case(key: &str) -> String {
+    let body = key.trim_start_matches('_');
+    let mut camel = key[..key.len() - body.len()].to_owned();
+    let mut upper = false;
+    for c in body.chars() {
+        match c {
+            '_' => upper = true,
+            c if upper => {
+                camel.extend(c.to_uppercase());
+                upper = false;
+            }
+            c => camel.push(c),
+        }
+    }
+    if upper {
+        camel.push('_');
+    }
+    camel
+}
+
+/// Renames `snake_case` object keys to `camelCase` at every level, keys
+/// without underscores are left as they are.
+fn camel_case_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| (camel_case(&key), camel_case_keys(value)))
+                .collect(),
+        ),
+        Value::Array(values) => Value::Array(values.into_iter().map(camel_case_keys).collect()),
+        value => value,
+    }
+}
+
+/// Decodes either the bare response or one wrapped in an [`Envelope`].
+fn decode_response<T: DeserializeOwned + Shape>(
+    value: &Value,
+    dev_mode: bool,
+) -> Result<T, String> {
+    let envelope = Envelope::parse(value);
+    if let Some(error) = envelope.as_ref().and_then(|e| e.error.clone()) {
+        return Err(error);
+    }
+    let error = match T::deserialize(value) {
+        Ok(data) => return Ok(data),
+        Err(e) => e,
+    };
+    let value = match envelope {
+        Some(envelope) => match T::deserialize(envelope.data) {
+            Ok(data) => return Ok(data),
+            Err(_) => envelope.data,
+        },
+        None => value,
+    };
+    if dev_mode {
+        let problems = schema::check(value, &T::SHAPE);
+        if !problems.is_empty() {
+            return Err(format!(
+                "Response doesn't match the expected shape:\n{}",
+                problems.join("\n")
+            ));
+        }
+    }
+    Err(format!("Failed to decode JSON response: {error}"))
+}
+
+/// FNV-1a, used instead of `DefaultHasher` because the value is persisted and
+/// has to stay the same across builds.
+fn stable_hash(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Recognizes servers that report a taken username in the body instead of a 409.
+fn is_username_taken(message: &str) -> bool {
+    let message = message.to_lowercase();
+    [
+        "already taken",
+        "already exists",
+        "username_taken",
+        "duplicate",
+    ]
+    .iter()
+    .any(|pattern| message.contains(pattern))
+}
+
+/// Reads the whole body, failing instead of buffering more than `limit_mib`.
+fn read_limited(response: Response, limit_mib: u64) -> Result<Vec<u8>, String> {
+    let limit_mib = limit_mib.max(1);
+    let limit = limit_mib.saturating_mul(1024 * 1024);
+    let mut body = Vec::new();
+    response
+        .into_reader()
+        .take(limit.saturating_add(1))
+        .read_to_end(&mut body)
+        .map_err(|e| format!("Failed to read response: {e}"))?;
+    if body.len() as u64 > limit {
+        return Err(format!("Response too large (over {limit_mib} MiB)"));
+    }
+    Ok(body)
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64)
+}
+
+/// Parses the HTTP-date form servers send, `Sun, 06 Nov 1994 08:49:37 GMT`,
+/// into a Unix timestamp.
+fn parse_http_date(text: &str) -> Option<i64> {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let [_, day, month, year, time, "GMT"] = text.split_whitespace().collect::<Vec<_>>()[..] else {
+        return None;
+    };
+    let day: i64 = day.parse().ok().filter(|day| (1..=31).contains(day))?;
+    let month = MONTHS.iter().position(|&m| m == month)? as i64 + 1;
+    let year: i64 = year.parse().ok()?;
+    let [hours, minutes, seconds] = time
+        .split(':')
+        .map(|part| part.parse::<i64>().ok())
+        .collect::<Option<Vec<_>>>()?[..]
+    else {
+        return None;
+    };
+    if hours > 23 || minutes > 59 || seconds > 60 {
+        return None;
+    }
+    // Days since 1970-01-01 in the Gregorian calendar, with years starting in
+    // March so the leap day comes last.
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+    Some(days * 86_400 + hours * 3600 + minutes * 60 + seconds)
+}
+
+fn format_duration(secs: i64) -> String {
+    let secs = secs.max(0);
+    match (secs / 86400, secs / 3600 % 24, secs / 60 % 60) {
+        (0, 0, 0) => format!("{secs}s"),
+        (0, 0, m) => format!("{m}m"),
+        (0, h, m) => format!("{h}h {m}m"),
+        (d, h, _) => format!("{d}d {h}h"),
+    }
+}
+
+/// Rough strength estimate from length and character variety, returns a 0-1 score and a label.
+fn password_strength(password: &str) -> (f32, &'static str) {
+    let len = password.chars().count();
+    let classes = [
+        password.chars().any(|c| c.is_lowercase()),
+        password.chars().any(|c| c.is_uppercase()),
+        password.chars().any(|c| c.is_ascii_digit()),
+        password.chars().any(|c| !c.is_alphanumeric()),
+    ]
+    .into_iter()
+    .filter(|&has| has)
+    .count();
+    let length_score = match len {
+        0..=5 => 0,
+        6..=7 => 1,
+        8..=11 => 2,
+        _ => 3,
+    };
+    let score = (length_score + classes.saturating_sub(1)).min(6);
+    let label = match score {
+        0..=1 => "Weak",
+        2..=3 => "Fair",
+        4..=5 => "Good",
+        _ => "Strong",
+    };
+    (score as f32 / 6.0, label)
+}
+
+/// Shows a "len/max" counter once the text gets close to its limit.
+fn length_counter(ui: &mut egui::Ui, text: &str, max: usize) {
+    let len = text.chars().count();
+    // Limits come from the server, a huge one must not overflow.
+    if len.saturating_mul(4) >= max.saturating_mul(3) {
+        ui.small(format!("{len}/{max}"));
+    }
+}
+
+fn check_mhf_folder(folder: &str) -> Result<(), String> {
+    if folder.trim().is_empty() {
+        return Err("No MHF folder configured, set one in Settings".into());
+    }
+    let exe = Path::new(folder).join("mhf.exe");
+    match fs::File::open(&exe) {
+        Ok(_) => Ok(()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Err(format!(
+            "mhf.exe not found in '{folder}', check the MHF folder in Settings"
+        )),
+        Err(e) => Err(format!("Unable to read '{}': {e}", exe.display())),
+    }
+}
+
+impl Tab {
+    fn get_host(&self) -> &str {
+        match self.host {
+            Host::LocalHost => "http://127.0.0.1:8080",
+            Host::Custom => &self.custom_host,
+        }
+    }
+
+    fn account_key(&self) -> String {
+        format!("{}@{}", self.username, self.get_host())
+    }
+}
+
+impl MhfLauncher {
+    fn is_secure_host(&self) -> bool {
+        self.tab.get_host().trim().starts_with("https://")
+    }
+
+    /// Plain HTTP to another machine, loopback traffic never leaves the PC.
+    fn is_insecure_connection(&self) -> bool {
+        let host = self.tab.get_host().trim();
+        let Some(rest) = host.strip_prefix("http://") else {
+            return false;
+        };
+        let authority = rest.split('/').next().unwrap_or_default();
+        let name = match authority.rsplit_once(':') {
+            Some((name, port)) if port.chars().all(|c| c.is_ascii_digit()) => name,
+            _ => authority,
+        };
+        !matches!(name, "localhost" | "[::1]") && !name.starts_with("127.")
+    }
+
+    fn render_insecure_banner(&mut self, ctx: &egui::Context) {
+        if self.tab.insecure_banner_dismissed
+            || !matches!(self.tab.state, MhfState::Character)
+            || !self.is_insecure_connection()
+        {
+            return;
+        }
+        egui::TopBottomPanel::top("insecure_warning").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.colored_label(
+                    ui.visuals().warn_fg_color,
+                    "🔓 Connected over plain HTTP, your password and session token are sent unencrypted",
+                );
+                if ui.small_button("Dismiss").clicked() {
+                    self.tab.insecure_banner_dismissed = true;
+                }
+            });
+        });
+    }
+
+    fn render_connection_security(&self, ui: &mut egui::Ui) {
+        if self.is_secure_host() {
+            ui.label("🔒").on_hover_text("Encrypted connection (HTTPS)");
+        } else {
+            ui.colored_label(ui.visuals().warn_fg_color, "🔓")
+                .on_hover_text("Plain HTTP: your password is sent to the server unencrypted");
+        }
+    }
+
+    fn handle_resposne<T: DeserializeOwned + Shape>(
+        &mut self,
+        response: Result<Response, ureq::Error>,
+    ) -> Option<T> {
+        self.tab.server_status = match &response {
+            Ok(_) | Err(ureq::Error::Status(..)) => ServerStatus::Online,
+            Err(_) => ServerStatus::Offline,
+        };
+        self.tab.last_status = match &response {
+            Ok(r) => Some(r.status()),
+            Err(ureq::Error::Status(code, _)) => Some(*code),
+            Err(_) => None,
+        };
+        match response {
+            Ok(r) => {
+                let data = read_limited(r, self.config.max_response_mib)
+                    .and_then(|body| {
+                        serde_json::from_slice::<Value>(&body)
+                            .map_err(|e| format!("Failed to decode JSON response: {e}"))
+                    })
+                    .map(
+                        |value| match self.config.field_casing.get(self.tab.get_host()) {
+                            Some(FieldCasing::SnakeCase) => camel_case_keys(value),
+                            _ => value,
+                        },
+                    )
+                    .and_then(|value| decode_response(&value, self.config.dev_mode));
+                match data {
+                    Ok(data) => {
+                        self.tab.error_message = None;
+                        return Some(data);
+                    }
+                    Err(e) => self.tab.error_message = Some(e),
+                };
+            }
+            Err(ureq::Error::Status(429, r)) => {
+                // Either seconds or a date, measured against the server's clock.
+                let retry_after = r
+                    .header("Retry-After")
+                    .and_then(|v| {
+                        v.trim().parse().ok().or_else(|| {
+                            let at = parse_http_date(v)?;
+                            Some(at.saturating_sub(self.server_now()).max(0) as u64)
+                        })
+                    })
+                    .unwrap_or(DEFAULT_RETRY_AFTER_SECS)
+                    .min(MAX_RETRY_AFTER_SECS);
+                let now = Instant::now();
+                self.tab.rate_limited_until = now
+                    .checked_add(Duration::from_secs(retry_after))
+                    .or_else(|| now.checked_add(Duration::from_secs(DEFAULT_RETRY_AFTER_SECS)));
+                self.tab.error_message = None;
+            }
+            Err(ureq::Error::Status(426, r)) => {
+                self.tab.update_required = Some(
+                    read_limited(r, self.config.max_response_mib)
+                        .ok()
+                        .and_then(|body| serde_json::from_slice(&body).ok())
+                        .unwrap_or_default(),
+                );
+                self.tab.error_message = None;
+            }
+            Err(ureq::Error::Status(_, r)) => {
+                let mut text = match read_limited(r, self.config.max_response_mib) {
+                    Ok(body) => String::from_utf8_lossy(&body).into_owned(),
+                    Err(e) => e,
+                };
+                if let Some(error) = serde_json::from_str(&text)
+                    .ok()
+                    .as_ref()
+                    .and_then(Envelope::parse)
+                    .and_then(|envelope| envelope.error)
+                {
+                    text = error;
+                }
+                if text.is_empty() {
+                    text = "Unable to connect to server, try again later".into();
+                }
+                self.tab.error_message = Some(text)
+            }
+            Err(e) => {
+                self.tab.connect_error = Some(e.to_string());
+                self.tab.error_message = Some("Failed to connect to server".to_owned());
+            }
+        };
+        if let (Some(error_message), Some(request_id)) =
+            (&mut self.tab.error_message, &self.tab.last_request_id)
+        {
+            error_message.push_str(&format!(" (request id {request_id})"));
+        }
+        None
+    }
+
+    /// Starts a POST to one of the configured endpoints, tagged with a fresh
+    /// `X-Request-Id` so failures can be matched against the server's logs.
+    fn post(&mut self, endpoint: Endpoint) -> ureq::Request {
+        self.request("POST", endpoint)
+    }
+
+    fn request(&mut self, method: &str, endpoint: Endpoint) -> ureq::Request {
+        let url = self.endpoint(self.config.endpoints.path(endpoint));
+        let timeout = self
+            .config
+            .endpoints
+            .timeouts
+            .get(&endpoint)
+            .copied()
+            .filter(|&timeout| timeout > 0)
+            .unwrap_or(self.config.request_timeout_secs)
+            // Hand-edited or imported configs skip the settings' limits.
+            .clamp(1, MAX_REQUEST_TIMEOUT_SECS);
+        let request_id = Uuid::new_v4().to_string();
+        let request = self
+            .agent()
+            .request(method, &url)
+            .set("X-Request-Id", &request_id)
+            .set("X-Launcher-Version", LAUNCHER_VERSION)
+            .timeout(Duration::from_secs(timeout));
+        self.tab.last_request_id = Some(request_id);
+        request
+    }
+
+    /// HTTP agent honouring the proxy setting. An invalid manual proxy falls
+    /// back to the system one, settings shows why.
+    fn agent(&self) -> ureq::Agent {
+        self.config.proxy.agent().unwrap_or_else(|e| {
+            log::warn!("{e}, using the system proxy instead");
+            ProxyMode::System.agent().unwrap_or_else(|_| ureq::agent())
+        })
+    }
+
+    fn endpoint(&self, path: &str) -> String {
+        format!(
+            "{}/{}",
+            self.tab.get_host().trim_end_matches('/'),
+            path.trim_start_matches('/')
+        )
+    }
+
+    fn validate_host(&self) -> Result<(), String> {
+        if self.tab.host != Host::Custom {
+            return Ok(());
+        }
+        let host = self.tab.custom_host.trim();
+        let rest = host
+            .strip_prefix("http://")
+            .or_else(|| host.strip_prefix("https://"))
+            .ok_or("Custom host must start with http:// or https://")?;
+        if rest.is_empty() || rest.contains(char::is_whitespace) {
+            return Err(format!("'{host}' is not a valid host"));
+        }
+        Ok(())
+    }
+
+    fn remember_host(&mut self) {
+        if self.tab.host != Host::Custom {
+            return;
+        }
+        self.config.push_recent_host(&self.tab.custom_host);
+    }
+
+    fn save_config(&mut self) {
+        self.config.host = self.tab.host;
+        self.config.custom_host = self.tab.custom_host.clone();
+        if !self.config.stay_logged_in {
+            self.config.session = None;
+        }
+        let json = self.config.to_json().ok();
+        if json.is_some() && json == self.saved_config {
+            return;
+        }
+        match self.config.save() {
+            Ok(()) => self.saved_config = json,
+            Err(e) => log::error!("Failed to save config: {e}"),
+        }
+    }
+
+    fn rate_limit_remaining(&self) -> Option<Duration> {
+        let remaining = self
+            .tab
+            .rate_limited_until?
+            .saturating_duration_since(Instant::now());
+        (!remaining.is_zero()).then_some(remaining)
+    }
+
+    fn render_rate_limit(&self, ui: &mut egui::Ui) {
+        if let Some(remaining) = self.rate_limit_remaining() {
+            ui.label(format!(
+                "Too many requests, try again in {}s",
+                remaining.as_secs() + 1
+            ));
+            ui.ctx().request_repaint_after(Duration::from_millis(250));
+        }
+    }
+
+    fn input_limits(&self) -> InputLimits {
+        self.config
+            .input_limits
+            .get(self.tab.get_host())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Current time on the server's clock, derived from the offset measured at login.
+    fn server_now(&self) -> i64 {
+        unix_now() + self.tab.server_offset
+    }
+
+    fn set_auth_data(&mut self, auth_data: AuthData) {
+        self.tab.last_refreshed = Some(Instant::now());
+        self.tab.server_offset = auth_data.current_ts as i64 - unix_now();
+        self.tab.auth_data = auth_data;
+        self.remember_host();
+        let host = self.tab.get_host().to_owned();
+        self.config.push_account(&host, &self.tab.username);
+        self.tab.pending_motd = self
+            .tab
+            .auth_data
+            .motd
+            .clone()
+            .filter(|motd| !motd.trim().is_empty())
+            .filter(|motd| self.config.seen_motd.get(&host) != Some(&stable_hash(motd)));
+        if self.config.stay_logged_in {
+            self.config.session = Some(SavedSession {
+                host: self.tab.get_host().to_owned(),
+                username: self.tab.username.clone(),
+                token: self.tab.auth_data.user.token.clone(),
+            });
+        }
+        if let Some(limits) = self.tab.auth_data.limits {
+            let host = self.tab.get_host().to_owned();
+            self.config.input_limits.insert(host, limits);
+        }
+        self.tab.banner = None;
+        self.tab.banner_request = None;
+        let banner_url = self
+            .tab
+            .auth_data
+            .branding
+            .as_ref()
+            .and_then(|b| b.banner_url.clone());
+        if let Some(url) = banner_url {
+            let agent = self.agent();
+            let (sender, receiver) = mpsc::channel();
+            thread::spawn(move || {
+                sender.send(branding::fetch_banner(&agent, &url)).ok();
+            });
+            self.tab.banner_request = Some(receiver);
+        }
+    }
+
+    fn poll_banner(&mut self, ctx: &egui::Context) {
+        let Some(receiver) = &self.tab.banner_request else {
+            return;
+        };
+        match receiver.try_recv() {
+            Ok(Ok(image)) => {
+                // Larger textures make the renderer panic.
+                let max_side = ctx.input(|i| i.max_texture_side);
+                if image.width() > max_side || image.height() > max_side {
+                    log::warn!("Server banner is larger than this GPU's {max_side}px textures");
+                } else {
+                    self.tab.banner = Some(ctx.load_texture("banner", image, Default::default()));
+                }
+                self.tab.banner_request = None;
+            }
+            Ok(Err(e)) => {
+                log::warn!("Failed to load server banner: {e}");
+                self.tab.banner_request = None;
+            }
+            Err(mpsc::TryRecvError::Empty) => ctx.request_repaint_after(Duration::from_millis(100)),
+            Err(mpsc::TryRecvError::Disconnected) => self.tab.banner_request = None,
+        }
+    }
+
+    /// Sends the login or registration request from a background thread so
+    /// the window keeps drawing while the server answers.
+    fn start_sign_in(&mut self, register: bool) {
+        let request = self.post(if register {
+            Endpoint::Register
+        } else {
+            Endpoint::Login
+        });
+        let username = self.tab.username.clone();
+        let password = self.tab.password.clone();
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let response = request.send_json(UserRequest {
+                username: &username,
+                password: &password,
+            });
+            sender.send(response).ok();
+        });
+        self.tab.sign_in = Some(SignIn {
+            kind: if register {
+                SignInKind::Register
+            } else {
+                SignInKind::Login
+            },
+            response: receiver,
+            token: None,
+        });
+        self.tab.state = MhfState::Connecting;
+    }
+
+    fn poll_sign_in(&mut self, ctx: &egui::Context) {
+        let Some(sign_in) = &self.tab.sign_in else {
+            return;
+        };
+        let response = match sign_in.response.try_recv() {
+            Ok(response) => response,
+            Err(mpsc::TryRecvError::Empty) => {
+                ctx.request_repaint_after(Duration::from_millis(100));
+                return;
+            }
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.tab.sign_in = None;
+                self.tab.state = MhfState::Login;
+                return;
+            }
+        };
+        let Some(SignIn { kind, token, .. }) = self.tab.sign_in.take() else {
+            return;
+        };
+        if let Some(token) = token {
+            if !self.apply_session_response(response, &token) {
+                if kind == SignInKind::Restore {
+                    self.config.session = None;
+                }
+                self.tab.state = MhfState::Login;
+            } else if kind == SignInKind::Token {
+                self.tab.token_input.clear();
+            }
+            return;
+        }
+        let register = kind == SignInKind::Register;
+        let signed_in = match self.handle_resposne(response) {
+            Some(auth_data) => self.accept_sign_in(auth_data),
+            None => {
+                if register
+                    && (self.tab.last_status == Some(409)
+                        || self
+                            .tab
+                            .error_message
+                            .as_deref()
+                            .is_some_and(is_username_taken))
+                {
+                    self.tab.error_message = Some("That username is already taken".into());
+                    self.tab.focus_username = true;
+                }
+                false
+            }
+        };
+        self.finish_sign_in(signed_in, register);
+    }
+
+    /// Dropping the receiver discards the answer if it still arrives.
+    fn cancel_sign_in(&mut self) {
+        self.tab.sign_in = None;
+        self.tab.state = MhfState::Login;
+    }
+
+    fn render_connecting(&mut self, ctx: &egui::Context) {
+        let host = match self.tab.host {
+            Host::Custom => self.tab.custom_host.trim(),
+            _ => self.tab.host.label(),
+        };
+        let action = match self.tab.sign_in.as_ref().map(|s| s.kind) {
+            Some(SignInKind::Register) => format!("Creating an account on {host}..."),
+            Some(SignInKind::Restore) => format!("Restoring your session on {host}..."),
+            _ => format!("Logging in to {host}..."),
+        };
+        let mut cancelled = false;
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.vertical_centered(|ui| {
+                ui.add_space(ui.spacing().item_spacing.y * 4.0);
+                ui.spinner();
+                ui.heading(action);
+                ui.weak(self.tab.get_host());
+                cancelled = ui.button("Cancel").clicked();
+            });
+        });
+        if cancelled || self.take_gamepad_action(gamepad::Action::Back) {
+            self.cancel_sign_in();
+        }
+    }
+
+    /// A response without a token would only fail later, when the game
+    /// presents it to the server.
+    fn accept_sign_in(&mut self, auth_data: AuthData) -> bool {
+        if auth_data.user.token.trim().is_empty() {
+            log::warn!(
+                "Sign-in response from {} had no session token",
+                self.tab.get_host()
+            );
+            self.tab.error_message = Some("Login incomplete — no session token received".into());
+            return false;
+        }
+        self.set_auth_data(auth_data);
+        true
+    }
+
+    fn restore_session(&mut self) {
+        if !self.config.stay_logged_in {
+            return;
+        }
+        let Some(session) = self.config.session.clone() else {
+            return;
+        };
+        if session.host != self.tab.get_host() {
+            return;
+        }
+        if session.token.trim().is_empty() {
+            self.config.session = None;
+            return;
+        }
+        self.tab.username = session.username;
+        self.start_session_check(SignInKind::Restore, session.token);
+    }
+
+    /// Signs in with an existing session token instead of a password.
+    fn start_session_check(&mut self, kind: SignInKind, token: String) {
+        let request = self.post(Endpoint::Me);
+        let sent = token.clone();
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let response = request.send_json(SessionRequest { token: &sent });
+            sender.send(response).ok();
+        });
+        self.tab.sign_in = Some(SignIn {
+            kind,
+            response: receiver,
+            token: Some(token),
+        });
+        self.tab.state = MhfState::Connecting;
+    }
+
+    fn apply_session_response(
+        &mut self,
+        response: Result<Response, ureq::Error>,
+        token: &str,
+    ) -> bool {
+        if let Ok(r) = &response {
+            self.tab.session_validators = SessionValidators {
+                etag: r.header("ETag").map(str::to_owned),
+                last_modified: r.header("Last-Modified").map(str::to_owned),
+            };
+        }
+        let result: Option<AuthData> = self.handle_resposne(response);
+        let Some(mut auth_data) = result else {
+            return false;
+        };
+        // The session answer may leave out the token it was asked with.
+        auth_data.user.token = token.to_owned();
+        if !self.accept_sign_in(auth_data) {
+            return false;
+        }
+        self.tab.state = MhfState::Character;
+        true
+    }
+
+    fn login_with_token(&mut self) {
+        if let Err(e) = self.validate_host() {
+            self.tab.error_message = Some(e);
+            return;
+        }
+        let token = self.tab.token_input.trim().to_owned();
+        if token.is_empty() {
+            self.tab.error_message = Some("Paste a token first".into());
+            return;
+        }
+        self.start_session_check(SignInKind::Token, token);
+    }
+
+    /// Checks the password with a login from a background thread.
+    fn request_reauth(&mut self) {
+        let Some(password) = self.tab.reauth.as_ref().map(|r| r.password.clone()) else {
+            return;
+        };
+        let username = self.tab.username.clone();
+        let request = self.post(Endpoint::Login);
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let response = request.send_json(UserRequest {
+                username: &username,
+                password: &password,
+            });
+            sender.send(response).ok();
+        });
+        if let Some(reauth) = &mut self.tab.reauth {
+            reauth.response = Some(receiver);
+        }
+    }
+
+    fn poll_reauth(&mut self, ctx: &egui::Context) {
+        let Some(receiver) = self.tab.reauth.as_ref().and_then(|r| r.response.as_ref()) else {
+            return;
+        };
+        let response = match receiver.try_recv() {
+            Ok(response) => response,
+            Err(mpsc::TryRecvError::Empty) => {
+                ctx.request_repaint_after(Duration::from_millis(100));
+                return;
+            }
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.tab.reauth = None;
+                return;
+            }
+        };
+        let result: Option<AuthData> = self.handle_resposne(response);
+        let Some(auth_data) = result else {
+            // Wrong password, the prompt stays open for another try.
+            if let Some(reauth) = &mut self.tab.reauth {
+                reauth.password.clear();
+                reauth.response = None;
+            }
+            return;
+        };
+        let Some(reauth) = self.tab.reauth.take() else {
+            return;
+        };
+        if !self.accept_sign_in(auth_data) {
+            return;
+        }
+        match reauth.action {
+            ReauthAction::Delete(character) => {
+                if self.config.password_retention == PasswordRetention::Session {
+                    self.tab.password = reauth.password;
+                }
+                self.request_delete_character(character);
+            }
+            ReauthAction::Start(character) => {
+                self.tab.password = reauth.password;
+                self.handle_start(character);
+            }
+        }
+    }
+
+    /// Asks for the session only if it changed since the last answer, for
+    /// servers that send an `ETag` or `Last-Modified` with it. Others always
+    /// answer in full. Sent from a background thread like a sign-in.
+    ///
+    /// The session is a POST, for which HTTP answers a matching `If-None-Match`
+    /// with 412 rather than 304. Servers may use either to mean unchanged, a
+    /// 412 is never a real precondition failure for this request.
+    fn refresh(&mut self, kind: RefreshKind) {
+        if self.tab.refresh.is_some() {
+            return;
+        }
+        let token = self.tab.auth_data.user.token.clone();
+        let mut request = self.post(Endpoint::Me);
+        if let Some(etag) = &self.tab.session_validators.etag {
+            request = request.set("If-None-Match", etag);
+        }
+        if let Some(last_modified) = &self.tab.session_validators.last_modified {
+            request = request.set("If-Modified-Since", last_modified);
+        }
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            sender
+                .send(request.send_json(SessionRequest { token: &token }))
+                .ok();
+        });
+        self.tab.refresh = Some(Refresh {
+            kind,
+            previous: self.tab.auth_data.characters.clone(),
+            response: receiver,
+        });
+    }
+
+    fn poll_refresh(&mut self, ctx: &egui::Context) {
+        let Some(refresh) = &self.tab.refresh else {
+            return;
+        };
+        let response = match refresh.response.try_recv() {
+            Ok(response) => response,
+            Err(mpsc::TryRecvError::Empty) => {
+                ctx.request_repaint_after(Duration::from_millis(100));
+                return;
+            }
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.tab.refresh = None;
+                return;
+            }
+        };
+        let Some(Refresh { kind, previous, .. }) = self.tab.refresh.take() else {
+            return;
+        };
+        let token = self.tab.auth_data.user.token.clone();
+        let unchanged = match &response {
+            Ok(r) if r.status() == 304 => Some(304),
+            Err(ureq::Error::Status(412, _)) => Some(412),
+            _ => None,
+        };
+        if let Some(status) = unchanged {
+            self.tab.server_status = ServerStatus::Online;
+            self.tab.last_status = Some(status);
+            self.tab.error_message = None;
+            self.tab.last_refreshed = Some(Instant::now());
+        } else if self.apply_session_response(response, &token) {
+            self.tab.character_changes = Some(CharacterChanges::between(
+                &previous,
+                &self.tab.auth_data.characters,
+            ));
+        }
+        // Failures that ended the session are shown on the login screen.
+        if !matches!(self.tab.state, MhfState::Character) {
+            return;
+        }
+        match kind {
+            RefreshKind::Manual => {
+                if let Some(message) = self.tab.error_message.take() {
+                    self.show_error("Refresh failed", message, Some(Retry::Refresh));
+                }
+            }
+            RefreshKind::Reconnect => {
+                if self.tab.server_status == ServerStatus::Online
+                    && self.tab.error_message.is_none()
+                {
+                    log::info!("Reconnected to {}", self.tab.get_host());
+                    self.show_toast("Reconnected");
+                }
+            }
+            RefreshKind::Auto => {}
+        }
+    }
+
+    fn logout(&mut self) {
+        self.tab.error_message = None;
+        // A late answer would otherwise log back in.
+        self.tab.refresh = None;
+        // The next account must not reuse a key meant for this one.
+        self.tab.create_request = None;
+        self.tab.create_key = None;
+        self.tab.session_validators = SessionValidators::default();
+        self.config.session = None;
+        self.tab.state = MhfState::Login;
+    }
+
+    fn switch_account(&mut self) {
+        self.logout();
+        self.tab.username.clear();
+        self.tab.password.clear();
+    }
+
+    fn login(&mut self) {
+        if self.rate_limit_remaining().is_some() {
+            return;
+        }
+        if let Err(e) = self.validate_host() {
+            self.tab.error_message = Some(e);
+            return;
+        }
+        self.start_sign_in(false);
+    }
+
+    fn register(&mut self) {
+        if self.rate_limit_remaining().is_some() {
+            return;
+        }
+        if let Err(e) = self.validate_host() {
+            self.tab.error_message = Some(e);
+            return;
+        }
+        self.start_sign_in(true);
+    }
+
+    /// Moves on to the character list only when signing in worked, a server
+    /// that can't be reached gets its own screen instead of a status line.
+    fn finish_sign_in(&mut self, signed_in: bool, register: bool) {
+        self.tab.state = if signed_in {
+            MhfState::Character
+        } else if self.tab.server_status == ServerStatus::Offline {
+            self.tab.retry_register = register;
+            MhfState::Unreachable
+        } else {
+            MhfState::Login
+        };
+    }
+
+    /// Creation isn't naturally idempotent, so every attempt for the same
+    /// character carries the same key and the server can return the original
+    /// character instead of creating a duplicate. The key is kept after a
+    /// transport failure so a manual retry reuses it too.
+    /// Sends the request from a background thread, retrying transport
+    /// failures with the same idempotency key so the server creates the
+    /// character only once.
+    fn request_create_character(&mut self) {
+        if self.tab.create_request.is_some() {
+            return;
+        }
+        let key = self
+            .tab
+            .create_key
+            .get_or_insert_with(|| Uuid::new_v4().to_string())
+            .clone();
+        let request = self
+            .post(Endpoint::CreateCharacter)
+            .set("Idempotency-Key", &key);
+        let token = self.tab.auth_data.user.token.clone();
+        let defaults = self.config.character_defaults;
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let mut attempt = 1;
+            let response = loop {
+                let response = request.clone().send_json(CreateCharRequest {
+                    token: &token,
+                    idempotency_key: &key,
+                    is_female: defaults.map(|d| d.is_female),
+                    weapon: defaults.map(|d| d.weapon),
+                });
+                match response {
+                    Err(ureq::Error::Transport(e)) if attempt < CREATE_ATTEMPTS => {
+                        log::warn!("Character creation attempt {attempt} failed, retrying: {e}");
+                        thread::sleep(CREATE_RETRY_DELAY * attempt as u32);
+                        attempt += 1;
+                    }
+                    response => break response,
+                }
+            };
+            sender.send(response).ok();
+        });
+        self.tab.create_request = Some(receiver);
+    }
+
+    fn poll_create_character(&mut self, ctx: &egui::Context) {
+        let Some(receiver) = &self.tab.create_request else {
+            return;
+        };
+        let response = match receiver.try_recv() {
+            Ok(response) => response,
+            Err(mpsc::TryRecvError::Empty) => {
+                ctx.request_repaint_after(Duration::from_millis(100));
+                return;
+            }
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.tab.create_request = None;
+                return;
+            }
+        };
+        self.tab.create_request = None;
+        if !matches!(response, Err(ureq::Error::Transport(_))) {
+            self.tab.create_key = None;
+        }
+        let result: Option<Character> = self.handle_resposne(response);
+        if let Some(character) = result {
+            self.handle_start(character);
+        };
+    }
+
+    fn request_delete_character(&mut self, character: Character) {
+        let request = self.post(Endpoint::DeleteCharacter);
+        let result: Option<Empty> = self.handle_resposne(request.send_json(DeleteCharRequest {
+            token: &self.tab.auth_data.user.token,
+            char_id: character.id,
+        }));
+        if let Some(Empty) = result {
+            self.tab
+                .auth_data
+                .characters
+                .retain(|c| c.id != character.id);
+        };
+    }
+
+    fn validate_mhf_folder(&mut self) {
+        self.folder_error = check_mhf_folder(&self.config.mhf_folder).err();
+    }
+
+    fn handle_start(&mut self, character: Character) {
+        if let Some(folder_error) = self.folder_error.clone() {
+            self.record_launch(
+                &self.tab.account_key(),
+                character.id,
+                Some(folder_error.clone()),
+            );
+            self.show_error("Unable to start the game", folder_error, None);
+            return;
+        }
+        self.tab.launch_failed = false;
+        if self.other_tab_launch() {
+            self.show_error(
+                "Unable to start the game",
+                "A game is already running from another tab",
+                None,
+            );
+            return;
+        }
+        if let Some(launch) = &self.tab.launch {
+            if launch.char_id == character.id {
+                self.tab.error_message = Some("This character is already in game".into());
+            }
+            return;
+        }
+        // Restored sessions, token logins and "Clear after launch" leave no
+        // password to hand to the game.
+        if self.tab.password.is_empty() {
+            self.tab.reauth = Some(Reauth {
+                action: ReauthAction::Start(character),
+                password: String::new(),
+                response: None,
+            });
+            return;
+        }
+        if self.config.dev_mode && self.dry_run {
+            let launch_data = LaunchData {
+                auth_data: self.tab.auth_data.clone(),
+                username: self.tab.username.clone(),
+                password: self.tab.password.clone(),
+                mhf_folder: self.config.mhf_folder.clone(),
+            };
+            self.dry_run_output = Some(describe_config(
+                &launch_data.build_config(&character),
+                self.export_secrets,
+            ));
+            self.show_toast(format!("Dry run: config built for {}", character.name));
+            return;
+        }
+        // The game runs inside this process and mhf_iel is only given its folder,
+        // so a custom working directory is set for the whole launcher until the
+        // game exits. Every relative path the launcher keeps using meanwhile is
+        // made absolute first, the config file already is.
+        let launcher_dir = env::current_dir().ok();
+        if let Some(dir) = &launcher_dir {
+            for path in [&mut self.config.mhf_folder, &mut self.config.game_workdir] {
+                if !path.trim().is_empty() && Path::new(path.trim()).is_relative() {
+                    *path = dir.join(path.trim()).to_string_lossy().into_owned();
+                }
+            }
+        }
+        let mhf_folder = self.config.mhf_folder.clone();
+        let mut restore_dir = None;
+        let workdir = self.config.game_workdir.trim();
+        if !workdir.is_empty() {
+            restore_dir = launcher_dir;
+            let error = if !Path::new(workdir).is_dir() {
+                Some(format!("Working directory does not exist: {workdir}"))
+            } else {
+                env::set_current_dir(workdir)
+                    .err()
+                    .map(|e| format!("Failed to change working directory: {e}"))
+            };
+            if let Some(error) = error {
+                self.record_launch(&self.tab.account_key(), character.id, Some(error.clone()));
+                self.show_error(
+                    "Unable to start the game",
+                    error,
+                    Some(Retry::Start(character.id)),
+                );
+                return;
+            }
+        }
+        let password = if self.config.password_retention == PasswordRetention::Never {
+            mem::take(&mut self.tab.password)
+        } else {
+            self.tab.password.clone()
+        };
+        let launch_data = LaunchData {
+            auth_data: self.tab.auth_data.clone(),
+            username: self.tab.username.clone(),
+            password,
+            mhf_folder,
+        };
+        let (sender, receiver) = mpsc::channel();
+        let character_id = character.id;
+        let character_name = character.display_name().into_owned();
+        thread::spawn(move || {
+            let result = check_mhf_folder(&launch_data.mhf_folder)
+                .map_err(LaunchError::NotFound)
+                .and_then(|()| {
+                    panic::catch_unwind(AssertUnwindSafe(|| launch_data.build_config(&character)))
+                        .map_err(|payload| LaunchError::Internal(panic_message(&*payload)))
+                })
+                // Only a panic inside mhf_iel points at the game's runtime.
+                .and_then(|config| {
+                    panic::catch_unwind(AssertUnwindSafe(|| mhf_iel::run(config)))
+                        .map_err(|payload| LaunchError::MissingRuntime(panic_message(&*payload)))
+                })
+                .and_then(|run| {
+                    run.map(|_| ())
+                        .map_err(|e| LaunchError::Failed(format!("{e:?}")))
+                });
+            if let Some(dir) = restore_dir {
+                if let Err(e) = env::set_current_dir(&dir) {
+                    log::warn!(
+                        "Failed to restore the working directory {}: {e}",
+                        dir.display()
+                    );
+                }
+            }
+            sender.send(result).ok();
+        });
+        self.tab.launch = Some(Launch {
+            char_id: character_id,
+            account: self.tab.account_key(),
+            character_name,
+            result: receiver,
+            detached: false,
+        });
+    }
+
+    /// Hides the wait for the current launch without touching the game. The
+    /// launch is still polled, and blocks starting another game until it ends.
+    fn detach_launch(&mut self) {
+        if let Some(launch) = &mut self.tab.launch {
+            log::info!("Detached from the launch of {}", launch.character_name);
+            launch.detached = true;
+            self.tab.launch_failed = false;
+        }
+    }
+
+    fn poll_launch(&mut self) {
+        let Some(launch) = &self.tab.launch else {
+            return;
+        };
+        let error = match launch.result.try_recv() {
+            Ok(Ok(())) => None,
+            Ok(Err(e)) => {
+                self.tab.launch_failed = true;
+                Some(e.to_string())
+            }
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => Some("The launch stopped unexpectedly".into()),
+        };
+        let Some(launch) = self.tab.launch.take() else {
+            return;
+        };
+        if let Some(error) = &error {
+            self.show_error(
+                "Launch failed",
+                format!("{}: {error}", launch.character_name),
+                Some(Retry::Start(launch.char_id)),
+            );
+        }
+        self.record_launch(&launch.account, launch.char_id, error);
+    }
+
+    fn record_launch(&mut self, account: &str, char_id: u32, error: Option<String>) {
+        match &error {
+            Some(e) => log::warn!("Launch of character {char_id} on {account} failed: {e}"),
+            None => log::info!("Launched character {char_id} on {account}"),
+        }
+        self.config
+            .character_views
+            .entry(account.to_owned())
+            .or_default()
+            .launches
+            .insert(
+                char_id,
+                LaunchRecord {
+                    at: unix_now(),
+                    error,
+                },
+            );
+    }
+
+    /// Asks where to save a file, falling back to the config directory when
+    /// no dialog can be shown. `None` means there is nowhere to save.
+    fn choose_save_path(
+        &mut self,
+        file_name: &str,
+        filter: &str,
+        extension: &str,
+    ) -> Option<PathBuf> {
+        match desktop::save_file(file_name, filter, extension) {
+            Ok(path) => path,
+            Err(e) => {
+                log::warn!("{e}");
+                let path = desktop::fallback_path(file_name);
+                if path.is_none() {
+                    self.show_toast(e);
+                }
+                path
+            }
+        }
+    }
+
+    fn copy_to_clipboard(&mut self, text: &str, what: &str) {
+        match desktop::copy_text(text) {
+            Ok(()) => self.show_toast(format!("{what} copied to clipboard")),
+            Err(e) => {
+                log::warn!("Clipboard unavailable: {e}");
+                self.show_toast("Clipboard unavailable");
+            }
+        }
+    }
+
+    fn export_auth_data(&mut self) {
+        let Some(path) = self.choose_save_path("auth_data.json", "JSON", "json") else {
+            return;
+        };
+        let mut value = match serde_json::to_value(&self.tab.auth_data) {
+            Ok(value) => value,
+            Err(e) => {
+                self.tab.error_message = Some(format!("Failed to encode auth data: {e}"));
+                return;
+            }
+        };
+        if !self.export_secrets {
+            value["user"]["token"] = "<redacted>".into();
+        }
+        let result = serde_json::to_string_pretty(&value)
+            .map_err(|e| e.to_string())
+            .and_then(|text| fs::write(&path, text).map_err(|e| e.to_string()));
+        match result {
+            Ok(()) => self.show_toast(format!("Auth data exported to {}", path.display())),
+            Err(e) => self.tab.error_message = Some(format!("Failed to export auth data: {e}")),
+        }
+    }
+
+    fn export_settings(&mut self) {
+        let Some(path) = self.choose_save_path("mhf-launcher-settings.json", "JSON", "json") else {
+            return;
+        };
+        let result = self
+            .config
+            .export_json(self.export_session)
+            .map_err(|e| e.to_string())
+            .and_then(|text| fs::write(&path, text).map_err(|e| e.to_string()));
+        match result {
+            Ok(()) => self.show_toast(format!("Settings exported to {}", path.display())),
+            Err(e) => {
+                log::error!("Failed to export settings: {e}");
+                self.show_toast(format!("Failed to export settings: {e}"));
+            }
+        }
+    }
+
+    fn import_settings(&mut self) {
+        let path = match desktop::open_file("JSON", "json") {
+            Ok(Some(path)) => path,
+            Ok(None) => return,
+            Err(e) => {
+                log::warn!("{e}");
+                self.show_toast(e);
+                return;
+            }
+        };
+        let result = fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| self.config.merged_with(&text));
+        match result {
+            Ok(config) => {
+                self.config = config;
+                self.tab.host = self.config.host;
+                self.tab.custom_host = self.config.custom_host.clone();
+                self.validate_mhf_folder();
+                self.show_toast(format!("Settings imported from {}", path.display()));
+            }
+            Err(e) => {
+                log::warn!("Failed to import {}: {e}", path.display());
+                self.show_toast(format!("Failed to import settings: {e}"));
+            }
+        }
+    }
+
+    fn render_settings(&mut self, ctx: &egui::Context) {
+        let mut folder_changed = false;
+        let mut dialog_error = None;
+        let mut open_folder = false;
+        let mut export_clicked = false;
+        let mut import_clicked = false;
+        let host = self.tab.get_host().to_owned();
+        egui::Window::new("Settings")
+            .open(&mut self.show_settings)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    folder_changed |= ui
+                        .text_edit_singleline(&mut self.config.mhf_folder)
+                        .labelled_by(ui.label("MHF Folder").id)
+                        .changed();
+                    let browse = ui.button("Browse...");
+                    browse.widget_info(|| {
+                        egui::WidgetInfo::labeled(egui::WidgetType::Button, "Browse for MHF folder")
+                    });
+                    if browse.clicked() {
+                        match desktop::pick_folder() {
+                            Ok(Some(folder)) => {
+                                self.config.mhf_folder = folder.display().to_string();
+                                folder_changed = true;
+                            }
+                            Ok(None) => {}
+                            Err(e) => dialog_error = Some(e),
+                        }
+                    }
+                    let open = ui
+                        .add_enabled(self.folder_error.is_none(), egui::Button::new("Open"))
+                        .on_hover_text("Open the game folder in the file explorer");
+                    open.widget_info(|| {
+                        egui::WidgetInfo::labeled(egui::WidgetType::Button, "Open game folder")
+                    });
+                    if open.clicked() {
+                        open_folder = true;
+                    }
+                });
+                if let Some(folder_error) = &self.folder_error {
+                    ui.colored_label(ui.visuals().error_fg_color, folder_error);
+                }
+                ui.text_edit_singleline(&mut self.config.game_workdir)
+                    .labelled_by(ui.label("Working Directory").id)
+                    .on_hover_text(
+                        "Directory the game is started from, leave empty for the default",
+                    );
+                ui.separator();
+                ui.add(
+                    egui::Slider::new(&mut self.config.ui_scale, UI_SCALE_RANGE)
+                        .text("UI scale")
+                        .fixed_decimals(2),
+                )
+                .on_hover_text("Scales every widget, independently of the layout's font size");
+                ui.horizontal(|ui| {
+                    ui.label("Layout");
+                    for density in [Density::Compact, Density::Comfortable] {
+                        ui.radio_value(&mut self.config.density, density, density.label());
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Password");
+                    for retention in [PasswordRetention::Session, PasswordRetention::Never] {
+                        ui.radio_value(
+                            &mut self.config.password_retention,
+                            retention,
+                            retention.label(),
+                        );
+                    }
+                })
+                .response
+                .on_hover_text("The password is never saved to disk");
+                ui.checkbox(&mut self.config.confirm_launch, "Confirm before launching");
+                ui.checkbox(
+                    &mut self.config.confirm_quit_while_running,
+                    "Confirm quitting while the game is running",
+                );
+                ui.checkbox(&mut self.config.start_minimized, "Start minimized")
+                    .on_hover_text("Useful together with \"Stay logged in\" when starting with Windows");
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::DragValue::new(&mut self.config.idle_logout_minutes)
+                            .clamp_range(0..=240)
+                            .suffix(" min"),
+                    );
+                    ui.label("Log out when idle");
+                })
+                .response
+                .on_hover_text("Return to the login screen after this long without input, 0 disables it");
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::DragValue::new(&mut self.config.auto_refresh_minutes)
+                            .clamp_range(0..=120)
+                            .suffix(" min"),
+                    );
+                    ui.label("Auto-refresh characters");
+                })
+                .response
+                .on_hover_text("Only while the launcher window is focused, 0 disables it");
+                ui.checkbox(&mut self.config.stay_logged_in, "Stay logged in")
+                    .on_hover_text("Restore the session on startup, deleting characters still asks for the password");
+                ui.checkbox(&mut self.config.gamepad, "Controller navigation")
+                    .on_hover_text("D-pad selects a character, A starts it and confirms, B cancels");
+                ui.checkbox(&mut self.config.discord_presence, "Discord Rich Presence")
+                    .on_hover_text("Show on Discord whether you're in the launcher or playing, and as whom");
+                if self.config.discord_presence {
+                    ui.horizontal(|ui| {
+                        ui.text_edit_singleline(&mut self.config.discord_application_id)
+                            .labelled_by(ui.label("Discord application ID").id)
+                            .on_hover_text("From the server's Discord application, or your own at discord.com/developers");
+                    });
+                }
+                ui.checkbox(&mut self.config.dev_mode, "Developer mode");
+                ui.collapsing(format!("Look for {host}"), |ui| {
+                    let theme = self.config.themes.entry(host.clone()).or_default();
+                    ui.horizontal(|ui| {
+                        ui.label("Theme");
+                        ui.radio_value(&mut theme.dark_mode, None, "Default");
+                        ui.radio_value(&mut theme.dark_mode, Some(true), "Dark");
+                        ui.radio_value(&mut theme.dark_mode, Some(false), "Light");
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Layout");
+                        ui.radio_value(&mut theme.density, None, "Global");
+                        for density in [Density::Compact, Density::Comfortable] {
+                            ui.radio_value(&mut theme.density, Some(density), density.label());
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        let mut custom = theme.accent_color.is_some();
+                        ui.checkbox(&mut custom, "Custom accent")
+                            .on_hover_text("Otherwise the server's accent color is used, if it has one");
+                        if !custom {
+                            theme.accent_color = None;
+                            return;
+                        }
+                        let mut rgb = theme
+                            .accent_color
+                            .as_deref()
+                            .and_then(branding::parse_color)
+                            .map_or([40, 120, 200], |c| [c.r(), c.g(), c.b()]);
+                        egui::widgets::color_picker::color_edit_button_srgb(ui, &mut rgb);
+                        theme.accent_color =
+                            Some(format!("#{:02x}{:02x}{:02x}", rgb[0], rgb[1], rgb[2]));
+                    });
+                    if *theme == ServerTheme::default() {
+                        self.config.themes.remove(&host);
+                    }
+                });
+                ui.horizontal(|ui| {
+                    let mut casing = self
+                        .config
+                        .field_casing
+                        .get(&host)
+                        .copied()
+                        .unwrap_or_default();
+                    ui.label("Response fields");
+                    ui.radio_value(&mut casing, FieldCasing::CamelCase, "camelCase");
+                    ui.radio_value(&mut casing, FieldCasing::SnakeCase, "snake_case")
+                        .on_hover_text("Keys are converted to camelCase before decoding");
+                    if casing == FieldCasing::default() {
+                        self.config.field_casing.remove(&host);
+                    } else {
+                        self.config.field_casing.insert(host.clone(), casing);
+                    }
+                });
+                ui.collapsing(format!("Password reset for {host}"), |ui| {
+                    let reset = self.config.password_reset.entry(host.clone()).or_default();
+                    ui.horizontal(|ui| {
+                        if ui.radio(*reset == PasswordReset::Disabled, "Disabled").clicked() {
+                            *reset = PasswordReset::Disabled;
+                        }
+                        if ui.radio(matches!(reset, PasswordReset::Url(_)), "Open URL").clicked()
+                            && !matches!(reset, PasswordReset::Url(_))
+                        {
+                            *reset = PasswordReset::Url(String::new());
+                        }
+                        if ui.radio(*reset == PasswordReset::Request, "Server request").clicked() {
+                            *reset = PasswordReset::Request;
+                        }
+                    });
+                    if let PasswordReset::Url(url) = reset {
+                        ui.text_edit_singleline(url)
+                            .labelled_by(ui.label("Reset URL").id);
+                    }
+                });
+                ui.horizontal(|ui| {
+                    export_clicked = ui.button("Export settings...").clicked();
+                    import_clicked = ui
+                        .button("Import settings...")
+                        .on_hover_text("Merged into the current settings, hosts and accounts are combined")
+                        .clicked();
+                    ui.checkbox(&mut self.export_session, "Include saved session")
+                        .on_hover_text("The session token logs in as you, only share it with yourself");
+                });
+                if ui.button("Reset launcher data...").clicked() {
+                    self.confirm_reset = true;
+                }
+                ui.collapsing("New characters", |ui| {
+                    let mut use_defaults = self.config.character_defaults.is_some();
+                    ui.checkbox(&mut use_defaults, "Send a default gender and weapon")
+                        .on_hover_text("Otherwise the server picks them until they're set in-game");
+                    let defaults = &mut self.config.character_defaults;
+                    match (use_defaults, defaults.is_some()) {
+                        (true, false) => *defaults = Some(CharacterDefaults::default()),
+                        (false, true) => *defaults = None,
+                        _ => {}
+                    }
+                    if let Some(defaults) = defaults {
+                        ui.horizontal(|ui| {
+                            ui.radio_value(&mut defaults.is_female, false, "Male");
+                            ui.radio_value(&mut defaults.is_female, true, "Female");
+                        });
+                        egui::ComboBox::from_label("Weapon")
+                            .selected_text(
+                                WEAPONS.get(defaults.weapon as usize).copied().unwrap_or("Unknown"),
+                            )
+                            .show_ui(ui, |ui| {
+                                for (id, name) in WEAPONS.iter().enumerate() {
+                                    ui.selectable_value(&mut defaults.weapon, id as u32, *name);
+                                }
+                            });
+                    }
+                });
+                ui.collapsing("Proxy", |ui| {
+                    let proxy = &mut self.config.proxy;
+                    ui.horizontal(|ui| {
+                        if ui.radio(*proxy == ProxyMode::System, "System").on_hover_text("Use the HTTPS_PROXY/HTTP_PROXY environment variables when set").clicked() {
+                            *proxy = ProxyMode::System;
+                        }
+                        if ui.radio(matches!(proxy, ProxyMode::Manual(_)), "Manual").clicked()
+                            && !matches!(proxy, ProxyMode::Manual(_))
+                        {
+                            *proxy = ProxyMode::Manual(String::new());
+                        }
+                        if ui.radio(*proxy == ProxyMode::Disabled, "None").clicked() {
+                            *proxy = ProxyMode::Disabled;
+                        }
+                    });
+                    if let ProxyMode::Manual(url) = proxy {
+                        ui.add(egui::TextEdit::singleline(url).hint_text("http://proxy.example:8080"))
+                            .labelled_by(ui.label("Proxy URL").id);
+                        if let Err(e) = ureq::Proxy::new(url.trim()) {
+                            ui.colored_label(ui.visuals().error_fg_color, format!("Invalid proxy: {e}"));
+                        }
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::DragValue::new(&mut self.config.request_timeout_secs)
+                            .clamp_range(1..=MAX_REQUEST_TIMEOUT_SECS)
+                            .suffix(" s"),
+                    );
+                    ui.label("Request timeout");
+                });
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::DragValue::new(&mut self.config.max_response_mib)
+                            .clamp_range(1..=256)
+                            .suffix(" MiB"),
+                    );
+                    ui.label("Largest accepted response")
+                        .on_hover_text("Bigger responses are rejected instead of read into memory");
+                });
+                ui.collapsing("Advanced: endpoint paths", |ui| {
+                    let endpoints = &mut self.config.endpoints;
+                    egui::Grid::new("endpoints").show(ui, |ui| {
+                        ui.label("");
+                        ui.label("Path");
+                        ui.label("Timeout");
+                        ui.end_row();
+                        for endpoint in Endpoint::ALL {
+                            ui.label(endpoint.label());
+                            ui.text_edit_singleline(endpoints.path_mut(endpoint));
+                            let mut timeout = endpoints.timeouts.get(&endpoint).copied().unwrap_or(0);
+                            let changed = ui
+                                .add(
+                                    egui::DragValue::new(&mut timeout)
+                                        .clamp_range(0..=MAX_REQUEST_TIMEOUT_SECS)
+                                        .custom_formatter(|v, _| {
+                                            if v == 0.0 { "default".into() } else { format!("{v} s") }
+                                        }),
+                                )
+                                .on_hover_text("0 uses the global request timeout")
+                                .changed();
+                            if changed && timeout == 0 {
+                                endpoints.timeouts.remove(&endpoint);
+                            } else if changed {
+                                endpoints.timeouts.insert(endpoint, timeout);
+                            }
+                            ui.end_row();
+                        }
+                    });
+                    if ui.button("Reset to defaults").clicked() {
+                        *endpoints = Default::default();
+                    }
+                });
+            });
+        if folder_changed {
+            self.validate_mhf_folder();
+        }
+        if open_folder {
+            if let Err(e) = desktop::open_folder(Path::new(&self.config.mhf_folder)) {
+                log::warn!("Failed to open {}: {e}", self.config.mhf_folder);
+                self.show_toast(format!("Unable to open the game folder: {e}"));
+            }
+        }
+        if export_clicked {
+            self.export_settings();
+        }
+        if import_clicked {
+            self.import_settings();
+        }
+        if let Some(e) = dialog_error {
+            log::warn!("{e}");
+            self.show_toast("File dialog unavailable, type the folder path instead");
+        }
+    }
+
+    fn render_dev_panel(&mut self, ui: &mut egui::Ui) {
+        ui.collapsing("Developer", |ui| {
+            ui.horizontal(|ui| {
+                if ui.button("Export auth data (JSON)").clicked() {
+                    self.export_auth_data();
+                }
+                ui.checkbox(&mut self.export_secrets, "Include secrets");
+            });
+            ui.label(format!(
+                "Clock skew: {}s (server ahead of local clock when positive)",
+                self.tab.server_offset
+            ));
+            let mut copy = None;
+            ui.horizontal(|ui| {
+                ui.label("Last request id:");
+                match &self.tab.last_request_id {
+                    Some(request_id) => {
+                        ui.monospace(request_id);
+                        if ui.small_button("Copy").clicked() {
+                            copy = Some((request_id.clone(), "Request id"));
+                        }
+                    }
+                    None => {
+                        ui.label("none yet");
+                    }
+                }
+            });
+            ui.checkbox(&mut self.dry_run, "Dry run").on_hover_text(
+                "Start builds the game config and shows it here instead of launching",
+            );
+            if let Some(output) = &self.dry_run_output {
+                ui.horizontal(|ui| {
+                    ui.label("Last dry run");
+                    if ui.button("Copy").clicked() {
+                        copy = Some((output.clone(), "Dry run"));
+                    }
+                });
+                egui::ScrollArea::vertical()
+                    .max_height(200.0)
+                    .show(ui, |ui| {
+                        ui.add(egui::Label::new(egui::RichText::new(output).monospace()));
+                    });
+            }
+            if let Some((text, what)) = copy {
+                self.copy_to_clipboard(&text, what);
+            }
+        });
+    }
+
+    fn render_timers(&self, ui: &mut egui::Ui) {
+        let now = self.server_now();
+        ui.horizontal(|ui| {
+            let expiry = self.tab.auth_data.expiry_ts as i64;
+            if expiry > now {
+                ui.label(format!(
+                    "Session expires in {}",
+                    format_duration(expiry - now)
+                ));
+            } else {
+                ui.label("Session expired");
+            }
+            ui.separator();
+            ui.label(format!("Entrances: {}", self.tab.auth_data.entrance_count))
+                .on_hover_text(
+                    "Entrance counter reported by the server at login. \
+                     The launcher passes it to the game as-is and never changes it.",
+                );
+            if let Some(refreshed) = self.tab.last_refreshed {
+                ui.separator();
+                ui.weak(format!(
+                    "Refreshed {} ago",
+                    format_duration(refreshed.elapsed().as_secs() as i64)
+                ));
+            }
+            if let Some(mez_fes) = &self.tab.auth_data.mez_fes {
+                ui.separator();
+                let (start, end) = (mez_fes.start as i64, mez_fes.end as i64);
+                if now < start {
+                    ui.label(format!("MezFes starts in {}", format_duration(start - now)));
+                } else if now < end {
+                    ui.label(format!("MezFes ends in {}", format_duration(end - now)));
+                } else {
+                    ui.label("MezFes has ended");
+                }
+            }
+        });
+        ui.ctx().request_repaint_after(Duration::from_secs(1));
+    }
+
+    fn render_login(&mut self, ctx: &egui::Context) {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            if let Some(banner) = &self.tab.banner {
+                ui.add(egui::Image::new(banner).max_height(120.0))
+                    .widget_info(|| {
+                        egui::WidgetInfo::labeled(egui::WidgetType::Label, "Server banner")
+                    });
+            }
+            ui.heading("Sample MHF Launcher");
+            let limits = self.input_limits();
+            let username = ui
+                .add(egui::TextEdit::singleline(&mut self.tab.username).char_limit(limits.username_max))
+                .labelled_by(ui.label("Username").id);
+            length_counter(ui, &self.tab.username, limits.username_max);
+            let password = ui
+                .add(egui::TextEdit::singleline(&mut self.tab.password).char_limit(limits.password_max))
+                .labelled_by(ui.label("Password").id);
+            if self.tab.focus_username || (!self.tab.login_shown && self.tab.username.is_empty()) {
+                username.request_focus();
+                self.tab.focus_username = false;
+            } else if !self.tab.login_shown {
+                password.request_focus();
+            }
+            self.tab.login_shown = true;
+            length_counter(ui, &self.tab.password, limits.password_max);
+            if !self.tab.password.is_empty() {
+                let (score, label) = password_strength(&self.tab.password);
+                let strength = ui.add(
+                    egui::ProgressBar::new(score)
+                        .text(format!("Password strength: {label}"))
+                        .desired_width(240.0),
+                );
+                strength.widget_info(|| {
+                    egui::WidgetInfo::labeled(
+                        egui::WidgetType::Label,
+                        format!("Password strength: {label}"),
+                    )
+                });
+                strength.on_hover_text("Used when registering, longer passwords mixing letters, digits and symbols are stronger");
+            }
+            ui.separator();
+
+            egui::ComboBox::from_label("Host")
+                .selected_text(self.tab.host.label())
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.tab.host, Host::LocalHost, Host::LocalHost.label());
+                    ui.selectable_value(&mut self.tab.host, Host::Custom, Host::Custom.label());
+                });
+            if ui.button("Copy server URL").clicked() {
+                let host = self.tab.get_host().to_owned();
+                self.copy_to_clipboard(&host, "Server URL");
+            }
+            if self.tab.host == Host::Custom {
+                let custom_host = ui
+                    .text_edit_singleline(&mut self.tab.custom_host)
+                    .labelled_by(ui.label("Custom Host").id);
+                if custom_host.lost_focus()
+                    && ui.input(|i| i.key_pressed(egui::Key::Enter))
+                    && !self.tab.username.is_empty()
+                    && !self.tab.password.is_empty()
+                {
+                    self.login();
+                }
+                if !self.config.recent_hosts.is_empty() {
+                    egui::ComboBox::from_label("Recent Hosts")
+                        .selected_text("Select...")
+                        .show_ui(ui, |ui| {
+                            for host in self.config.recent_hosts.iter() {
+                                if ui
+                                    .selectable_label(*host == self.tab.custom_host, host)
+                                    .clicked()
+                                {
+                                    self.tab.custom_host = host.clone();
+                                }
+                            }
+                        });
+                }
+            }
+            ui.horizontal(|ui| {
+                self.render_connection_security(ui);
+                if self.is_secure_host() {
+                    ui.label("Encrypted connection");
+                    if ui.button("View certificate").clicked() {
+                        self.request_certificate();
+                    }
+                } else {
+                    ui.colored_label(ui.visuals().warn_fg_color, "Unencrypted connection");
+                }
+            });
+            ui.separator();
+            ui.horizontal(|ui| {
+                let can_request = self.rate_limit_remaining().is_none();
+                if ui
+                    .add_enabled(can_request, egui::Button::new("Login"))
+                    .clicked()
+                {
+                    self.login();
+                }
+                if ui
+                    .add_enabled(can_request, egui::Button::new("Register"))
+                    .clicked()
+                {
+                    self.register();
+                }
+                if ui.button("Settings").clicked() {
+                    self.show_settings = true;
+                }
+                if ui.button("Diagnostics").clicked() {
+                    self.open_diagnostics();
+                }
+            });
+            // After the login fields, so Tab goes through those first.
+            ui.separator();
+            let accounts = self.config.accounts.get(self.tab.get_host());
+            if let Some(accounts) = accounts.filter(|a| !a.is_empty()) {
+                egui::ComboBox::from_label("Accounts")
+                    .selected_text("Select...")
+                    .show_ui(ui, |ui| {
+                        for account in accounts.iter() {
+                            if ui
+                                .selectable_label(*account == self.tab.username, account)
+                                .clicked()
+                            {
+                                self.tab.username = account.clone();
+                                self.tab.password.clear();
+                            }
+                        }
+                    });
+            }
+            ui.horizontal(|ui| {
+                // Hidden as soon as it holds a password, the host can be checked after connecting.
+                let has_password = self
+                    .tab
+                    .quick_connect
+                    .rsplit_once('@')
+                    .is_some_and(|(user_info, _)| user_info.contains(':'));
+                let quick_connect = ui
+                    .add(
+                        egui::TextEdit::singleline(&mut self.tab.quick_connect)
+                            .hint_text("user:password@host")
+                            .password(has_password),
+                    )
+                    .labelled_by(ui.label("Quick connect").id)
+                    .on_hover_text("Paste an account and host or a launcher link");
+                let submitted =
+                    quick_connect.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                if ui
+                    .add_enabled(
+                        !self.tab.quick_connect.trim().is_empty(),
+                        egui::Button::new("Connect"),
+                    )
+                    .clicked()
+                    || submitted
+                {
+                    self.quick_connect();
+                }
+            });
+            let reset = match self.config.password_reset.get(self.tab.get_host()) {
+                Some(PasswordReset::Url(url)) if url.is_empty() => None,
+                Some(PasswordReset::Disabled) | None => None,
+                reset => reset,
+            };
+            if let Some(reset) = reset {
+                if ui.link("Forgot password?").clicked() {
+                    match reset {
+                        PasswordReset::Url(url) => ui.ctx().open_url(egui::OpenUrl::new_tab(url)),
+                        _ => {
+                            self.reset_username = self.tab.username.clone();
+                            self.show_reset_dialog = true;
+                        }
+                    }
+                }
+            }
+            ui.collapsing("Advanced: log in with token", |ui| {
+                ui.text_edit_singleline(&mut self.tab.token_input)
+                    .labelled_by(ui.label("Token").id);
+                if ui
+                    .add_enabled(
+                        self.rate_limit_remaining().is_none(),
+                        egui::Button::new("Log in with token"),
+                    )
+                    .clicked()
+                {
+                    self.login_with_token();
+                }
+            });
+            self.render_rate_limit(ui);
+            if let Some(error_message) = &self.tab.error_message {
+                ui.label(error_message);
+            }
+        });
+    }
+
+    fn render_notifications(&mut self, ui: &mut egui::Ui) {
+        if self.tab.auth_data.notifications.is_empty() {
+            return;
+        }
+        let mut toggle = None;
+        egui::CollapsingHeader::new("Announcements")
+            .default_open(true)
+            .show(ui, |ui| {
+                for (index, notification) in self.tab.auth_data.notifications.iter().enumerate() {
+                    let important = notification.flags != 0;
+                    let expanded = self.tab.expanded_notifications.contains(&index);
+                    let text = if expanded {
+                        Cow::Borrowed(notification.data.as_str())
+                    } else {
+                        truncate_chars(&notification.data, MAX_NOTIFICATION_CHARS)
+                    };
+                    let long = expanded || matches!(text, Cow::Owned(_));
+                    egui::Frame::group(ui.style()).show(ui, |ui| {
+                        ui.set_width(ui.available_width());
+                        if important {
+                            ui.visuals_mut().override_text_color = Some(ui.visuals().warn_fg_color);
+                        }
+                        for line in text.lines() {
+                            render_markup(ui, line, important);
+                        }
+                        if long
+                            && ui
+                                .link(if expanded { "Show less" } else { "Show more" })
+                                .clicked()
+                        {
+                            toggle = Some(index);
+                        }
+                    });
+                }
+            });
+        if let Some(index) = toggle {
+            if !self.tab.expanded_notifications.remove(&index) {
+                self.tab.expanded_notifications.insert(index);
+            }
+        }
+        ui.separator();
+    }
+
+    fn render_characters(&mut self, ctx: &egui::Context) {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            self.render_notifications(ui);
+            self.render_timers(ui);
+            let can_request = self.rate_limit_remaining().is_none();
+            let can_launch = self.folder_error.is_none()
+                && self.tab.launch.is_none()
+                && self.tab.create_request.is_none()
+                && !self.other_tab_launch()
+                && can_request;
+            let char_limit = self.tab.auth_data.char_limit.unwrap_or(DEFAULT_CHAR_LIMIT);
+            let char_count = self.tab.auth_data.characters.len();
+            let at_limit = char_count >= char_limit;
+            let mut selected = None;
+            if self.tab.auth_data.characters.is_empty() {
+                ui.vertical_centered(|ui| {
+                    ui.add_space(ui.spacing().item_spacing.y * 4.0);
+                    ui.label("No characters yet — create one to get started");
+                    let mut create = ui.add_enabled(
+                        can_launch,
+                        egui::Button::new(egui::RichText::new("Create Character").heading()),
+                    );
+                    if let Some(folder_error) = &self.folder_error {
+                        create = create.on_disabled_hover_text(folder_error);
+                    }
+                    if create.clicked() {
+                        self.request_create_character();
+                    }
+                });
+                ui.separator();
+            }
+            let mut recent: Vec<&Character> = self
+                .tab
+                .auth_data
+                .characters
+                .iter()
+                .filter(|c| c.last_login > 0 && !c.is_new)
+                .collect();
+            recent.sort_by_key(|c| std::cmp::Reverse(c.last_login));
+            recent.truncate(RECENT_CHARACTERS);
+            if !recent.is_empty() {
+                ui.label("Recently played");
+                ui.horizontal(|ui| {
+                    for character in recent {
+                        ui.group(|ui| {
+                            ui.vertical(|ui| {
+                                ui.strong(character.display_name());
+                                ui.weak(format!("HR {} · GR {}", character.hr, character.gr));
+                                let launch = ui.add_enabled(
+                                    can_launch,
+                                    egui::Button::new(egui::RichText::new("Launch").heading()),
+                                );
+                                launch.widget_info(|| {
+                                    egui::WidgetInfo::labeled(
+                                        egui::WidgetType::Button,
+                                        format!("Launch {}", character.name),
+                                    )
+                                });
+                                if launch.clicked() {
+                                    selected = Some((character.clone(), CharacterOp::Start));
+                                }
+                            });
+                        });
+                    }
+                });
+                ui.separator();
+            }
+            let account = self.tab.account_key();
+            let mut toggle_pin = None;
+            let mut save_note = false;
+            let view = self
+                .config
+                .character_views
+                .entry(account.clone())
+                .or_default();
+            ui.horizontal(|ui| {
+                ui.text_edit_singleline(&mut view.filter)
+                    .labelled_by(ui.label("Filter").id);
+                egui::ComboBox::from_label("Sort by")
+                    .selected_text(view.sort_key.label())
+                    .show_ui(ui, |ui| {
+                        for key in SortKey::ALL {
+                            ui.selectable_value(&mut view.sort_key, key, key.label());
+                        }
+                    });
+                ui.checkbox(&mut view.descending, "Descending");
+            });
+            let view = view.clone();
+            let server_now = self.server_now();
+            let visible_count = view.apply(&self.tab.auth_data.characters).len();
+            if self.take_gamepad_action(gamepad::Action::Up) {
+                self.gamepad_selection = self.gamepad_selection.saturating_sub(1);
+            }
+            if self.take_gamepad_action(gamepad::Action::Down) {
+                self.gamepad_selection += 1;
+            }
+            self.gamepad_selection = self.gamepad_selection.min(visible_count.saturating_sub(1));
+            // While the confirm dialog is open, A belongs to it.
+            let gamepad_start = can_launch
+                && self.tab.pending_start.is_none()
+                && self.take_gamepad_action(gamepad::Action::Confirm);
+            let gamepad_selection = self.gamepad.is_some().then_some(self.gamepad_selection);
+            let fade = self
+                .tab
+                .character_changes
+                .as_ref()
+                .map_or(0.0, CharacterChanges::fade);
+            if fade > 0.0 {
+                ui.ctx().request_repaint();
+            } else {
+                self.tab.character_changes = None;
+            }
+            if let Some(changes) = &self.tab.character_changes {
+                if !changes.removed.is_empty() {
+                    ui.label(
+                        egui::RichText::new(format!("Removed: {}", changes.removed.join(", ")))
+                            .color(ui.visuals().error_fg_color.gamma_multiply(fade)),
+                    );
+                }
+            }
+            // A fixed id keeps the scroll offset stable while the list changes.
+            let list_height = (ui.available_height() - ui.spacing().interact_size.y * 6.0)
+                .max(ui.spacing().interact_size.y * 3.0);
+            egui::ScrollArea::vertical()
+                .id_source("character_list")
+                .max_height(list_height)
+                .show(ui, |ui| {
+                    for (index, character) in view
+                        .apply(&self.tab.auth_data.characters)
+                        .into_iter()
+                        .enumerate()
+                    {
+                        let gamepad_selected = gamepad_selection == Some(index);
+                        if gamepad_selected && gamepad_start {
+                            selected = Some((character.clone(), CharacterOp::Start));
+                        }
+                        let highlight = self.tab.character_changes.as_ref().and_then(|changes| {
+                            if changes.added.contains(&character.id) {
+                                Some(egui::Color32::from_rgb(40, 160, 60))
+                            } else if changes.changed.contains(&character.id) {
+                                Some(ui.visuals().warn_fg_color)
+                            } else {
+                                None
+                            }
+                        });
+                        let background = ui.painter().add(egui::Shape::Noop);
+                        let row = ui.horizontal(|ui| {
+                            let pinned = view.pinned.contains(&character.id);
+                            let pin = ui
+                                .selectable_label(pinned, if pinned { "★" } else { "☆" })
+                                .on_hover_text(if pinned { "Unpin" } else { "Pin to the top" });
+                            pin.widget_info(|| {
+                                egui::WidgetInfo::selected(
+                                    egui::WidgetType::Checkbox,
+                                    pinned,
+                                    format!("Pin {}", character.name),
+                                )
+                            });
+                            if pin.clicked() {
+                                toggle_pin = Some(character.id);
+                            }
+                            if character.is_new {
+                                ui.visuals_mut().override_text_color =
+                                    Some(ui.visuals().weak_text_color());
+                            }
+                            ui.label("ID:");
+                            ui.label(&character.id.to_string());
+                            ui.label("Name:");
+                            ui.label(character.display_name());
+                            if let Some(record) = view.launches.get(&character.id) {
+                                let ago = format_duration(unix_now() - record.at);
+                                let (color, text) = match &record.error {
+                                    Some(e) => (
+                                        ui.visuals().error_fg_color,
+                                        format!("Last launch failed {ago} ago: {e}"),
+                                    ),
+                                    None => (
+                                        egui::Color32::from_rgb(40, 160, 60),
+                                        format!("Last launched {ago} ago"),
+                                    ),
+                                };
+                                let size = ui.text_style_height(&egui::TextStyle::Body) * 0.5;
+                                let (rect, dot) = ui.allocate_exact_size(
+                                    egui::vec2(size, size),
+                                    egui::Sense::hover(),
+                                );
+                                ui.painter().circle_filled(rect.center(), size * 0.5, color);
+                                dot.on_hover_text(text);
+                            }
+                            match &mut self.tab.editing_note {
+                                Some((id, draft)) if *id == character.id => {
+                                    let note = ui.add(
+                                        egui::TextEdit::singleline(draft)
+                                            .hint_text("Note")
+                                            .desired_width(120.0),
+                                    );
+                                    if self.tab.focus_note {
+                                        note.request_focus();
+                                        self.tab.focus_note = false;
+                                    }
+                                    save_note |=
+                                        note.lost_focus() || ui.small_button("✔").clicked();
+                                }
+                                _ => {
+                                    let note = view.notes.get(&character.id);
+                                    if let Some(note) = note {
+                                        ui.weak(format!("({note})"));
+                                    }
+                                    let edit = ui.small_button("✏").on_hover_text("Edit note");
+                                    edit.widget_info(|| {
+                                        egui::WidgetInfo::labeled(
+                                            egui::WidgetType::Button,
+                                            format!("Edit note for {}", character.name),
+                                        )
+                                    });
+                                    if edit.clicked() {
+                                        self.tab.editing_note =
+                                            Some((character.id, note.cloned().unwrap_or_default()));
+                                        self.tab.focus_note = true;
+                                    }
+                                }
+                            }
+                            ui.separator();
+                            ui.label("HR");
+                            ui.label(&character.hr.to_string());
+                            ui.separator();
+                            ui.label("GR");
+                            ui.label(&character.gr.to_string());
+                            if character.last_login > 0 {
+                                ui.separator();
+                                ui.label(format!(
+                                    "Last login {} ago",
+                                    format_duration(server_now - character.last_login)
+                                ));
+                            }
+                            let mut start = ui.add_enabled(
+                                can_launch,
+                                egui::Button::new(if character.is_new {
+                                    "Set up"
+                                } else {
+                                    "Start"
+                                }),
+                            );
+                            if character.is_new {
+                                start = start.on_hover_text(
+                                    "This character still needs to be created in-game",
+                                );
+                            }
+                            if let Some(folder_error) = &self.folder_error {
+                                start = start.on_disabled_hover_text(folder_error);
+                            } else if self
+                                .tab
+                                .launch
+                                .as_ref()
+                                .is_some_and(|l| l.char_id == character.id)
+                            {
+                                start = start
+                                    .on_disabled_hover_text("This character is already in game");
+                            }
+                            start.widget_info(|| {
+                                let action = if character.is_new { "Set up" } else { "Start" };
+                                egui::WidgetInfo::labeled(
+                                    egui::WidgetType::Button,
+                                    format!("{action} {}", character.name),
+                                )
+                            });
+                            if start.clicked() {
+                                selected = Some((character.clone(), CharacterOp::Start));
+                            }
+                            let delete = ui.add_enabled(can_request, egui::Button::new("Deleted"));
+                            delete.widget_info(|| {
+                                egui::WidgetInfo::labeled(
+                                    egui::WidgetType::Button,
+                                    format!("Delete {}", character.name),
+                                )
+                            });
+                            if delete.clicked() {
+                                selected = Some((character.clone(), CharacterOp::Delete));
+                            }
+                            let copy = ui.button("Copy info");
+                            copy.widget_info(|| {
+                                egui::WidgetInfo::labeled(
+                                    egui::WidgetType::Button,
+                                    format!("Copy info for {}", character.name),
+                                )
+                            });
+                            if copy.clicked() {
+                                selected = Some((character.clone(), CharacterOp::CopyInfo));
+                            }
+                        });
+                        if let Some(color) = highlight {
+                            ui.painter().set(
+                                background,
+                                egui::Shape::rect_filled(
+                                    row.response.rect.expand(2.0),
+                                    2.0,
+                                    color.gamma_multiply(fade * 0.35),
+                                ),
+                            );
+                        }
+                        if gamepad_selected {
+                            ui.painter().rect_stroke(
+                                row.response.rect.expand(2.0),
+                                2.0,
+                                ui.visuals().selection.stroke,
+                            );
+                            row.response.scroll_to_me(None);
+                        }
+                        ui.separator();
+                    }
+                });
+            if save_note {
+                if let Some((id, note)) = self.tab.editing_note.take() {
+                    let notes = &mut self
+                        .config
+                        .character_views
+                        .entry(account.clone())
+                        .or_default()
+                        .notes;
+                    match note.trim() {
+                        "" => notes.remove(&id),
+                        note => notes.insert(id, note.to_owned()),
+                    };
+                }
+            }
+            if let Some(id) = toggle_pin {
+                let pinned = &mut self
+                    .config
+                    .character_views
+                    .entry(account)
+                    .or_default()
+                    .pinned;
+                if let Some(index) = pinned.iter().position(|&p| p == id) {
+                    pinned.remove(index);
+                } else {
+                    pinned.push(id);
+                }
+            }
+            if let Some((character, op)) = selected {
+                match op {
+                    CharacterOp::Start => self.start_character(character),
+                    CharacterOp::Delete if self.config.stay_logged_in => {
+                        self.tab.reauth = Some(Reauth {
+                            action: ReauthAction::Delete(character),
+                            password: String::new(),
+                            response: None,
+                        })
+                    }
+                    CharacterOp::Delete => self.request_delete_character(character),
+                    CharacterOp::CopyInfo => {
+                        self.copy_to_clipboard(&character.details(server_now), "Character info")
+                    }
+                };
+            }
+            ui.label(format!("Characters: {char_count}/{char_limit}"));
+            ui.horizontal(|ui| {
+                let mut create =
+                    ui.add_enabled(can_launch && !at_limit, egui::Button::new("Create"));
+                if let Some(folder_error) = &self.folder_error {
+                    create = create.on_disabled_hover_text(folder_error);
+                } else if at_limit {
+                    create = create.on_disabled_hover_text(
+                        "All character slots are in use, delete a character to create a new one",
+                    );
+                }
+                if create.clicked() {
+                    self.request_create_character();
+                }
+                if ui
+                    .add_enabled(
+                        can_request && self.tab.refresh.is_none(),
+                        egui::Button::new("Refresh"),
+                    )
+                    .clicked()
+                {
+                    self.refresh(RefreshKind::Manual);
+                }
+                if ui.button("Logout").clicked() {
+                    self.logout();
+                }
+                if ui.button("Switch Account").clicked() {
+                    self.switch_account();
+                }
+                if ui.button("Settings").clicked() {
+                    self.show_settings = true;
+                }
+                if ui.button("Diagnostics").clicked() {
+                    self.open_diagnostics();
+                }
+                if ui.button("Mini mode").clicked() {
+                    self.set_mini_mode(true);
+                }
+            });
+            if let Some(launch) = self.tab.launch.as_ref().filter(|l| !l.detached) {
+                let mut detach = false;
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(format!(
+                        "Launching Monster Hunter Frontier as {}...",
+                        launch.character_name
+                    ));
+                    detach = ui
+                        .button("Back to launcher")
+                        .on_hover_text("Stop waiting for the game, it keeps running")
+                        .clicked();
+                    if ui.button("Force close game").clicked() {
+                        self.tab.confirm_force_close = true;
+                    }
+                });
+                if detach {
+                    self.detach_launch();
+                }
+            } else if let Some(launch) = &self.tab.launch {
+                ui.horizontal(|ui| {
+                    ui.label(format!("{} is in game", launch.character_name));
+                    if ui.button("Force close game").clicked() {
+                        self.tab.confirm_force_close = true;
+                    }
+                });
+            }
+            if self.config.dev_mode {
+                self.render_dev_panel(ui);
+            }
+            self.render_rate_limit(ui);
+            if let Some(error_message) = &self.tab.error_message {
+                ui.label(error_message);
+            }
+            if self.tab.launch_failed && ui.button("Troubleshoot").clicked() {
+                self.open_diagnostics();
+            }
+        });
+    }
+
+    fn start_character(&mut self, character: Character) {
+        if self.config.confirm_launch {
+            self.tab.pending_start = Some(character);
+        } else {
+            self.handle_start(character);
+        }
+    }
+
+    fn quick_connect(&mut self) {
+        let quick = deeplink::parse_quick_connect(&self.tab.quick_connect);
+        // It may hold a password either way, so it doesn't stay on screen.
+        self.tab.quick_connect.clear();
+        let quick = match quick {
+            Ok(quick) => quick,
+            Err(e) => {
+                self.tab.error_message = Some(format!("Quick connect: {e}"));
+                return;
+            }
+        };
+        self.tab.custom_host = quick.host;
+        self.tab.host = Host::Custom;
+        if quick.username.is_empty() {
+            self.tab.focus_username = true;
+            return;
+        }
+        self.tab.username = quick.username;
+        match quick.password {
+            Some(password) => {
+                self.tab.password = password;
+                self.login();
+            }
+            None => {
+                self.tab.password.clear();
+                self.show_toast(format!("Enter the password for {}", self.tab.username));
+            }
+        }
+    }
+
+    fn open_deep_link(&mut self, link: deeplink::DeepLink) {
+        log::info!("Opened from a launcher link to {}", link.host);
+        self.tab.custom_host = link.host;
+        self.tab.host = Host::Custom;
+        self.tab.focus_username = true;
+    }
+
+    fn set_mini_mode(&mut self, mini_mode: bool) {
+        self.config.mini_mode = mini_mode;
+        self.resize_window = true;
+    }
+
+    /// Compact layout with just the connection status and a button starting
+    /// the character at the top of the list.
+    fn render_mini(&mut self, ctx: &egui::Context) {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                let size = ui.text_style_height(&egui::TextStyle::Body) * 0.5;
+                let (rect, _) =
+                    ui.allocate_exact_size(egui::vec2(size, size), egui::Sense::hover());
+                ui.painter().circle_filled(
+                    rect.center(),
+                    size * 0.5,
+                    self.tab.server_status.color(),
+                );
+                self.render_connection_security(ui);
+                ui.label(self.tab.get_host());
+            });
+            ui.horizontal(|ui| {
+                let first = matches!(self.tab.state, MhfState::Character)
+                    .then(|| {
+                        let view = self.config.character_views.get(&self.tab.account_key());
+                        let view = view.cloned().unwrap_or_default();
+                        view.apply(&self.tab.auth_data.characters)
+                            .first()
+                            .map(|c| (*c).clone())
+                    })
+                    .flatten();
+                if let Some(launch) = &self.tab.launch {
+                    ui.spinner();
+                    ui.label(format!("{} in game", launch.character_name));
+                } else if let Some(character) = first {
+                    let can_launch =
+                        self.folder_error.is_none() && self.rate_limit_remaining().is_none();
+                    if ui
+                        .add_enabled(
+                            can_launch,
+                            egui::Button::new(format!("Start {}", character.display_name())),
+                        )
+                        .clicked()
+                    {
+                        self.start_character(character);
+                    }
+                } else {
+                    ui.label("Not logged in");
+                }
+                if ui.button("Expand").clicked() {
+                    self.set_mini_mode(false);
+                }
+            });
+        });
+    }
+
+    fn request_password_reset(&mut self) {
+        let request = self.post(Endpoint::ResetPassword);
+        let result: Option<Empty> = self.handle_resposne(request.send_json(ResetRequest {
+            username: &self.reset_username,
+        }));
+        if result.is_some() {
+            self.show_reset_dialog = false;
+            self.show_toast(
+                "Password reset requested, check your email or the server's instructions",
+            );
+        }
+    }
+
+    fn render_reset_dialog(&mut self, ctx: &egui::Context) {
+        let mut send = false;
+        egui::Window::new("Reset Password")
+            .open(&mut self.show_reset_dialog)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.text_edit_singleline(&mut self.reset_username)
+                    .labelled_by(ui.label("Username or email").id);
+                send = ui
+                    .add_enabled(
+                        !self.reset_username.trim().is_empty(),
+                        egui::Button::new("Send reset request"),
+                    )
+                    .clicked();
+                if let Some(error_message) = &self.tab.error_message {
+                    ui.label(error_message);
+                }
+            });
+        if send {
+            self.request_password_reset();
+        }
+    }
+
+    fn render_confirm_start(&mut self, ctx: &egui::Context) {
+        let Some(character) = &self.tab.pending_start else {
+            return;
+        };
+        let mut confirmed = false;
+        let mut cancelled = false;
+        egui::Window::new("Confirm Launch")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(format!("Launch {}?", character.display_name()));
+                ui.horizontal(|ui| {
+                    confirmed = ui.button("Launch").clicked();
+                    cancelled = ui.button("Cancel").clicked();
+                });
+            });
+        confirmed |= self.take_gamepad_action(gamepad::Action::Confirm);
+        cancelled |= self.take_gamepad_action(gamepad::Action::Back);
+        if confirmed {
+            if let Some(character) = self.tab.pending_start.take() {
+                self.handle_start(character);
+            }
+        } else if cancelled {
+            self.tab.pending_start = None;
+        }
+    }
+
+    fn show_error(
+        &mut self,
+        title: &'static str,
+        message: impl Into<String>,
+        retry: Option<Retry>,
+    ) {
+        let message = message.into();
+        // Kept on disk in case the launcher doesn't get to exit cleanly. Inline
+        // validation messages aren't worth reporting at the next start.
+        let mut redacted = message.clone();
+        for secret in [&self.tab.password, &self.tab.auth_data.user.token] {
+            if !secret.is_empty() {
+                redacted = redacted.replace(secret.as_str(), "<redacted>");
+            }
+        }
+        last_error::record(&redacted);
+        self.tab.error_dialog = Some(ErrorDialog {
+            title,
+            message,
+            retry,
+        });
+    }
+
+    fn render_error_dialog(&mut self, ctx: &egui::Context) {
+        let Some(dialog) = &self.tab.error_dialog else {
+            return;
+        };
+        let mut dismissed = false;
+        let mut retried = false;
+        egui::Window::new(dialog.title)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(&dialog.message);
+                ui.horizontal(|ui| {
+                    dismissed = ui.button("OK").clicked();
+                    if dialog.retry.is_some() {
+                        retried = ui.button("Retry").clicked();
+                    }
+                });
+            });
+        dismissed |= self.take_gamepad_action(gamepad::Action::Back);
+        retried |= self.take_gamepad_action(gamepad::Action::Confirm);
+        if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+            dismissed = true;
+        }
+        if !(dismissed || retried) {
+            return;
+        }
+        let Some(dialog) = self.tab.error_dialog.take() else {
+            return;
+        };
+        match dialog.retry.filter(|_| retried) {
+            Some(Retry::Start(char_id)) => {
+                let character = self
+                    .tab
+                    .auth_data
+                    .characters
+                    .iter()
+                    .find(|c| c.id == char_id)
+                    .cloned();
+                if let Some(character) = character {
+                    self.handle_start(character);
+                }
+            }
+            Some(Retry::Refresh) => self.refresh(RefreshKind::Manual),
+            None => {}
+        }
+    }
+
+    fn render_previous_error(&mut self, ctx: &egui::Context) {
+        let Some(previous) = &self.previous_error else {
+            return;
+        };
+        let mut dismissed = false;
+        let mut troubleshoot = false;
+        egui::Window::new("Last session ended with an error")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(&previous.message);
+                ui.weak(format!("{} ago", format_duration(unix_now() - previous.at)));
+                ui.horizontal(|ui| {
+                    dismissed = ui.button("OK").clicked();
+                    troubleshoot = ui.button("Troubleshoot").clicked();
+                });
+            });
+        if troubleshoot {
+            self.open_diagnostics();
+        }
+        if dismissed || troubleshoot {
+            self.previous_error = None;
+        }
+    }
+
+    fn render_force_close(&mut self, ctx: &egui::Context) {
+        if !self.tab.confirm_force_close {
+            return;
+        }
+        // The game may have exited while the dialog was open.
+        let Some(launch) = &self.tab.launch else {
+            self.tab.confirm_force_close = false;
+            self.show_toast("The game already exited");
+            return;
+        };
+        let mut confirmed = false;
+        let mut cancelled = false;
+        egui::Window::new("Force Close Game")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "Force close the game for {}? Unsaved progress may be lost.",
+                    launch.character_name
+                ));
+                ui.label("The game runs inside the launcher, so the launcher will close too.");
+                ui.horizontal(|ui| {
+                    confirmed = ui.button("Force close").clicked();
+                    cancelled = ui.button("Cancel").clicked();
+                });
+            });
+        if confirmed {
+            log::warn!("Force closing the game for {}", launch.character_name);
+            // Exiting here skips `on_exit`, the config is flushed by hand.
+            self.save_config();
+            // Closing on purpose, not a failure to report at the next start.
+            last_error::clear();
+            process::exit(1);
+        } else if cancelled {
+            self.tab.confirm_force_close = false;
+        }
+    }
+
+    fn render_confirm_quit(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        if !self.confirm_quit {
+            return;
+        }
+        // Nothing left to interrupt, finish the quit that was asked for.
+        if self.tab.launch.is_none() {
+            self.quit_confirmed = true;
+            frame.close();
+            return;
+        }
+        let mut confirmed = false;
+        let mut cancelled = false;
+        egui::Window::new("Quit Launcher")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label("The game is still running — quit launcher anyway?");
+                ui.label("The game runs inside the launcher, so it will close too.");
+                ui.checkbox(&mut self.quit_dont_ask, "Don't ask again");
+                ui.horizontal(|ui| {
+                    confirmed = ui.button("Quit").clicked();
+                    cancelled = ui.button("Cancel").clicked();
+                });
+            });
+        if confirmed {
+            if self.quit_dont_ask {
+                self.config.confirm_quit_while_running = false;
+            }
+            self.quit_confirmed = true;
+            frame.close();
+        } else if cancelled {
+            self.confirm_quit = false;
+        }
+    }
+
+    /// Shows the release notes once after the launcher was updated. A fresh
+    /// install has nothing to compare against and starts quietly.
+    fn check_whats_new(&mut self) {
+        let previous = self
+            .config
+            .last_run_version
+            .replace(LAUNCHER_VERSION.to_owned());
+        if previous.is_some_and(|previous| previous != LAUNCHER_VERSION) {
+            self.whats_new = release_notes(LAUNCHER_VERSION);
+        }
+    }
+
+    fn render_whats_new(&mut self, ctx: &egui::Context) {
+        let Some(notes) = self.whats_new else {
+            return;
+        };
+        let mut dismissed = false;
+        egui::Window::new(format!("What's new in v{LAUNCHER_VERSION}"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        for line in notes.lines() {
+                            let line = line
+                                .strip_prefix("- ")
+                                .map_or(line.to_owned(), |item| format!("• {item}"));
+                            render_markup(ui, &line, false);
+                        }
+                    });
+                dismissed = ui.button("Got it").clicked();
+            });
+        if dismissed {
+            self.whats_new = None;
+        }
+    }
+
+    fn render_motd(&mut self, ctx: &egui::Context) {
+        let Some(motd) = &self.tab.pending_motd else {
+            return;
+        };
+        let mut dismissed = false;
+        egui::Window::new(format!("Welcome to {}", self.tab.get_host()))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        for line in motd.lines() {
+                            render_markup(ui, line, false);
+                        }
+                    });
+                dismissed = ui.button("Got it").clicked();
+            });
+        if dismissed {
+            let host = self.tab.get_host().to_owned();
+            let hash = stable_hash(motd);
+            self.config.seen_motd.insert(host, hash);
+            self.tab.pending_motd = None;
+        }
+    }
+
+    fn render_confirm_reset(&mut self, ctx: &egui::Context) {
+        if !self.confirm_reset {
+            return;
+        }
+        let mut confirmed = false;
+        let mut cancelled = false;
+        egui::Window::new("Reset Launcher Data")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label("Delete all saved settings, accounts, sessions, notes and pins?");
+                ui.label("The launcher returns to its first-run state. This can't be undone.");
+                ui.horizontal(|ui| {
+                    confirmed = ui.button("Reset").clicked();
+                    cancelled = ui.button("Cancel").clicked();
+                });
+            });
+        if confirmed {
+            self.confirm_reset = false;
+            self.reset_launcher_data();
+        } else if cancelled {
+            self.confirm_reset = false;
+        }
+    }
+
+    fn reset_launcher_data(&mut self) {
+        // Before the config goes, the error file lives next to it.
+        last_error::clear();
+        if let Err(e) = Config::delete() {
+            log::error!("Failed to delete config: {e}");
+            self.show_toast(format!("Failed to delete saved data: {e}"));
+            return;
+        }
+        log::warn!("Launcher data reset");
+        self.switch_account();
+        self.config = Config::default();
+        self.saved_config = None;
+        self.tab.host = self.config.host;
+        self.tab.custom_host = self.config.custom_host.clone();
+        self.tab.auth_data = AuthData::default();
+        self.tab.character_changes = None;
+        self.validate_mhf_folder();
+        self.show_toast("Launcher data reset");
+    }
+
+    fn render_reauth(&mut self, ctx: &egui::Context) {
+        let Some(reauth) = &mut self.tab.reauth else {
+            return;
+        };
+        let checking = reauth.response.is_some();
+        let mut confirmed = false;
+        let mut cancelled = false;
+        egui::Window::new("Confirm Password")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(match &reauth.action {
+                    ReauthAction::Delete(character) => {
+                        format!(
+                            "Enter your password to delete {}.",
+                            character.display_name()
+                        )
+                    }
+                    ReauthAction::Start(character) => {
+                        format!("Enter your password to start {}.", character.display_name())
+                    }
+                });
+                let password = ui.add_enabled(
+                    !checking,
+                    egui::TextEdit::singleline(&mut reauth.password).password(true),
+                );
+                if password.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    confirmed = true;
+                }
+                if let Some(error) = &self.tab.error_message {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+                ui.horizontal(|ui| {
+                    confirmed |= ui
+                        .add_enabled(!checking, egui::Button::new("Confirm"))
+                        .clicked();
+                    cancelled = ui.button("Cancel").clicked();
+                    if checking {
+                        ui.spinner();
+                    }
+                });
+            });
+        if confirmed && !checking {
+            self.tab.error_message = None;
+            self.request_reauth();
+        } else if cancelled {
+            // Dropping the receiver discards the answer if it still arrives.
+            self.tab.reauth = None;
+        }
+    }
+
+    fn bug_report(&self) -> String {
+        let checks: String = self
+            .tab
+            .diagnostics
+            .iter()
+            .map(|check| match &check.result {
+                Ok(details) => format!("PASS {}: {details}\n", check.name),
+                Err(e) => format!("FAIL {}: {e}\n", check.name),
+            })
+            .collect();
+        format!(
+            "## Description\n\
+             <Describe what happened and what you expected to happen>\n\n\
+             ## Steps to reproduce\n\
+             1. \n\n\
+             ## Environment\n\
+             Launcher version: {}\n\
+             OS: {} ({})\n\
+             Host: {}\n\n\
+             ## Diagnostics\n\
+             {checks}\n\
+             ## Config (redacted)\n\
+             {}\n\n\
+             ## Recent log\n\
+             {}\n",
+            LAUNCHER_VERSION,
+            env::consts::OS,
+            env::consts::ARCH,
+            self.tab.get_host(),
+            self.config.redacted_json(),
+            logger::tail().join("\n"),
+        )
+    }
+
+    fn save_bug_report(&mut self) {
+        let Some(path) = self.choose_save_path("mhf-launcher-report.txt", "Text", "txt") else {
+            return;
+        };
+        match fs::write(&path, self.bug_report()) {
+            Ok(()) => self.show_toast(format!("Bug report saved to {}", path.display())),
+            Err(e) => self.show_toast(format!("Failed to save bug report: {e}")),
+        }
+    }
+
+    fn open_diagnostics(&mut self) {
+        self.tab.diagnostics_return = self.tab.state;
+        self.tab.state = MhfState::Diagnostics;
+        self.run_diagnostics();
+    }
+
+    fn run_diagnostics(&mut self) {
+        let mhf_folder = self.config.mhf_folder.clone();
+        let host = self.tab.get_host().to_owned();
+        let agent = self.agent();
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            sender
+                .send(diagnostics::run_all(&agent, &mhf_folder, &host))
+                .ok();
+        });
+        self.tab.diagnostics.clear();
+        self.tab.diagnostics_request = Some(receiver);
+    }
+
+    fn verify_game_files(&mut self) {
+        let mhf_folder = self.config.mhf_folder.clone();
+        let max_response_mib = self.config.max_response_mib;
+        let request = self.request("GET", Endpoint::Manifest);
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let result = check_mhf_folder(&mhf_folder).and_then(|()| {
+                let response = request
+                    .call()
+                    .map_err(|e| format!("Unable to fetch the file list: {e}"))?;
+                let manifest: integrity::Manifest =
+                    serde_json::from_slice(&read_limited(response, max_response_mib)?)
+                        .map_err(|e| format!("Invalid file list: {e}"))?;
+                Ok(integrity::verify(&mhf_folder, &manifest))
+            });
+            sender.send(result).ok();
+        });
+        self.verify_result = None;
+        self.verify_request = Some(receiver);
+    }
+
+    fn render_verify(&mut self, ctx: &egui::Context) {
+        if let Some(receiver) = &self.verify_request {
+            match receiver.try_recv() {
+                Ok(result) => {
+                    if let Err(e) = &result {
+                        log::warn!("File verification failed: {e}");
+                    }
+                    self.verify_result = Some(result);
+                    self.verify_request = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => {
+                    ctx.request_repaint_after(Duration::from_millis(100))
+                }
+                Err(mpsc::TryRecvError::Disconnected) => self.verify_request = None,
+            }
+        }
+        if self.verify_request.is_none() && self.verify_result.is_none() {
+            return;
+        }
+        let mut closed = false;
+        egui::Window::new("Verify Game Files")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                match &self.verify_result {
+                    None => {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label("Checking files, this can take a few minutes...");
+                        });
+                    }
+                    Some(Err(e)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, e);
+                    }
+                    Some(Ok(report)) if report.problems.is_empty() => {
+                        ui.label(format!("All {} files are intact.", report.checked));
+                    }
+                    Some(Ok(report)) => {
+                        ui.colored_label(
+                            ui.visuals().error_fg_color,
+                            format!(
+                                "{} of {} files are missing or damaged:",
+                                report.problems.len(),
+                                report.checked
+                            ),
+                        );
+                        egui::ScrollArea::vertical()
+                            .max_height(240.0)
+                            .show(ui, |ui| {
+                                for problem in &report.problems {
+                                    ui.label(problem);
+                                }
+                            });
+                        ui.small("Reinstall or repair the game client to restore these files.");
+                    }
+                }
+                // Closing mid-check drops the receiver, the result is discarded.
+                closed = ui.button("Close").clicked();
+            });
+        if closed {
+            self.verify_request = None;
+            self.verify_result = None;
+        }
+    }
+
+    fn render_diagnostics(&mut self, ctx: &egui::Context) {
+        if self.take_gamepad_action(gamepad::Action::Back) {
+            self.tab.state = self.tab.diagnostics_return;
+        }
+        if let Some(receiver) = &self.tab.diagnostics_request {
+            if let Ok(checks) = receiver.try_recv() {
+                self.tab.diagnostics = checks;
+                self.tab.diagnostics_request = None;
+            }
+        }
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.heading("Diagnostics");
+            if self.tab.diagnostics_request.is_some() {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label("Running checks...");
+                });
+            }
+            for check in self.tab.diagnostics.iter() {
+                ui.horizontal(|ui| match &check.result {
+                    Ok(details) => {
+                        ui.colored_label(egui::Color32::GREEN, "PASS");
+                        ui.label(check.name);
+                        ui.weak(details);
+                    }
+                    Err(e) => {
+                        ui.colored_label(ui.visuals().error_fg_color, "FAIL");
+                        ui.label(check.name);
+                        ui.weak(e);
+                    }
+                });
+                if check.result.is_err() {
+                    ui.small(check.hint);
+                }
+                ui.separator();
+            }
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(
+                        self.tab.diagnostics_request.is_none(),
+                        egui::Button::new("Run again"),
+                    )
+                    .clicked()
+                {
+                    self.run_diagnostics();
+                }
+                if ui
+                    .add_enabled(
+                        self.verify_request.is_none(),
+                        egui::Button::new("Verify game files"),
+                    )
+                    .on_hover_text("Compare the MHF folder against the server's file list")
+                    .clicked()
+                {
+                    self.verify_game_files();
+                }
+                if ui.button("Back").clicked() {
+                    self.tab.state = self.tab.diagnostics_return;
+                }
+            });
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.label("Report a Bug");
+                if ui.button("Copy report").clicked() {
+                    let report = self.bug_report();
+                    self.copy_to_clipboard(&report, "Bug report");
+                }
+                if ui.button("Save report...").clicked() {
+                    self.save_bug_report();
+                }
+            });
+        });
+    }
+
+    fn render_unreachable(&mut self, ctx: &egui::Context) {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.vertical_centered(|ui| {
+                ui.add_space(ui.spacing().item_spacing.y * 4.0);
+                ui.heading("Can't reach server");
+                ui.label(self.tab.get_host());
+                if let Some(error) = &self.tab.connect_error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+                if let Some(request_id) = &self.tab.last_request_id {
+                    ui.weak(format!("Request id {request_id}"));
+                }
+                ui.add_space(ui.spacing().item_spacing.y * 2.0);
+                let can_request = self.rate_limit_remaining().is_none();
+                if ui
+                    .add_enabled(can_request, egui::Button::new("Retry"))
+                    .clicked()
+                {
+                    if self.tab.retry_register {
+                        self.register();
+                    } else {
+                        self.login();
+                    }
+                }
+                if ui.link("Change host").clicked() {
+                    self.tab.error_message = None;
+                    self.tab.state = MhfState::Login;
+                }
+                if ui.link("Diagnostics").clicked() {
+                    self.open_diagnostics();
+                }
+            });
+        });
+    }
+
+    fn render_update_required(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        let Some(update) = &self.tab.update_required else {
+            return;
+        };
+        let mut back = false;
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.vertical_centered(|ui| {
+                ui.heading("Please update your launcher");
+                ui.label(match &update.min_version {
+                    Some(min_version) => format!(
+                        "{} requires launcher version {min_version} or newer, you have {LAUNCHER_VERSION}.",
+                        self.tab.get_host()
+                    ),
+                    None => format!(
+                        "{} no longer supports launcher version {LAUNCHER_VERSION}.",
+                        self.tab.get_host()
+                    ),
+                });
+                match &update.download_url {
+                    Some(url) => {
+                        ui.hyperlink_to("Download the latest version", url);
+                    }
+                    None => {
+                        ui.label("Ask the server operators where to get the latest version.");
+                    }
+                }
+                ui.horizontal(|ui| {
+                    back = ui.button("Choose another server").clicked();
+                    if ui.button("Quit").clicked() {
+                        frame.close();
+                    }
+                });
+            });
+        });
+        if back {
+            self.tab.update_required = None;
+            self.logout();
+        }
+    }
+
+    /// Starts or stops Discord presence with the setting and reports what
+    /// the launcher is doing.
+    fn update_presence(&mut self) {
+        let application_id = self.config.discord_application_id.trim();
+        if !self.config.discord_presence || application_id.is_empty() {
+            self.presence = None;
+            return;
+        }
+        let activity = if let Some(launch) = &self.tab.launch {
+            presence::Activity::Playing {
+                character: launch.character_name.clone(),
+                hr: self
+                    .tab
+                    .auth_data
+                    .characters
+                    .iter()
+                    .find(|c| c.id == launch.char_id)
+                    .map_or(0, |c| c.hr),
+            }
+        } else if matches!(self.tab.state, MhfState::Character) {
+            presence::Activity::Browsing {
+                host: self.tab.get_host().to_owned(),
+            }
+        } else {
+            presence::Activity::Login
+        };
+        self.presence
+            .get_or_insert_with(|| presence::Presence::new(application_id.to_owned()))
+            .set(activity);
+    }
+
+    /// Refreshes the character screen on the configured interval, but only
+    /// while someone could be looking at it.
+    fn auto_refresh(&mut self, ctx: &egui::Context, focused: bool) {
+        let minutes = self.config.auto_refresh_minutes;
+        if minutes == 0
+            || !focused
+            || !matches!(self.tab.state, MhfState::Character)
+            || self.tab.server_status == ServerStatus::Offline
+            || self.rate_limit_remaining().is_some()
+            || self.tab.refresh.is_some()
+        {
+            return;
+        }
+        let interval = Duration::from_secs(minutes as u64 * 60);
+        // A failed refresh waits a full interval too instead of retrying every frame.
+        let last = self.tab.last_refreshed.max(self.tab.last_auto_refresh);
+        let now = Instant::now();
+        let due = last.map_or(now, |last| last + interval);
+        if now < due {
+            ctx.request_repaint_after(due - now);
+            return;
+        }
+        self.tab.last_auto_refresh = Some(now);
+        self.refresh(RefreshKind::Auto);
+    }
+
+    /// While logged in, checks on the server in the background so losing it is
+    /// noticed without a request failing, and refreshes the session once it
+    /// answers again.
+    fn watch_connection(&mut self, ctx: &egui::Context) {
+        if !matches!(self.tab.state, MhfState::Character) {
+            self.tab.connection_check = None;
+            self.tab.next_connection_check = None;
+            return;
+        }
+        let offline = self.tab.server_status == ServerStatus::Offline;
+        if let Some(receiver) = &self.tab.connection_check {
+            match receiver.try_recv() {
+                Ok(reachable) => {
+                    self.tab.connection_check = None;
+                    if offline && reachable {
+                        self.tab.next_connection_check = None;
+                        self.refresh(RefreshKind::Reconnect);
+                    } else if !offline && !reachable {
+                        log::info!("Lost connection to {}", self.tab.get_host());
+                        self.tab.server_status = ServerStatus::Offline;
+                        self.tab.next_connection_check = None;
+                    }
+                }
+                Err(mpsc::TryRecvError::Disconnected) => self.tab.connection_check = None,
+                Err(mpsc::TryRecvError::Empty) => {
+                    ctx.request_repaint_after(Duration::from_millis(100))
+                }
+            }
+            return;
+        }
+        if self.tab.refresh.is_some() {
+            return;
+        }
+        let interval = if offline {
+            RECONNECT_INTERVAL
+        } else {
+            HEALTH_CHECK_INTERVAL
+        };
+        // Going offline must not wait out the rest of the slower interval.
+        let now = Instant::now();
+        let next = self
+            .tab
+            .next_connection_check
+            .map_or(now + interval, |next| next.min(now + interval));
+        self.tab.next_connection_check = Some(next);
+        if now < next {
+            ctx.request_repaint_after(next - now);
+            return;
+        }
+        let agent = self.agent();
+        let host = self.tab.get_host().to_owned();
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            sender
+                .send(diagnostics::reachable(&agent, &host).is_ok())
+                .ok();
+        });
+        self.tab.connection_check = Some(receiver);
+        self.tab.next_connection_check = Some(now + interval);
+    }
+
+    /// Logs out after `idle_logout_minutes` without input, warning during the
+    /// last [`IDLE_WARNING`] so the user can stay by moving the mouse.
+    fn handle_idle(&mut self, ctx: &egui::Context) {
+        let now = Instant::now();
+        let user_input = ctx.input(|i| {
+            i.events.iter().any(|event| {
+                matches!(
+                    event,
+                    egui::Event::PointerMoved(_)
+                        | egui::Event::PointerButton { .. }
+                        | egui::Event::Scroll(_)
+                        | egui::Event::Zoom(_)
+                        | egui::Event::MouseWheel { .. }
+                        | egui::Event::Touch { .. }
+                        | egui::Event::Key { .. }
+                        | egui::Event::Text(_)
+                )
+            })
+        });
+        // Input goes to the game while it runs, so the timer starts over once it exits.
+        if user_input || self.last_input.is_none() || self.tab.launch.is_some() {
+            self.last_input = Some(now);
+        }
+        let minutes = self.config.idle_logout_minutes;
+        if minutes == 0
+            || self.tab.launch.is_some()
+            || !matches!(self.tab.state, MhfState::Character)
+        {
+            return;
+        }
+        let timeout = Duration::from_secs(minutes as u64 * 60);
+        let idle = self.last_input.map_or(Duration::ZERO, |t| now - t);
+        let remaining = timeout.saturating_sub(idle);
+        if remaining.is_zero() {
+            log::warn!("Logging out after {minutes} minute(s) of inactivity");
+            self.logout();
+            self.tab.auth_data = AuthData::default();
+            self.tab.password.clear();
+            self.show_toast("Logged out due to inactivity");
+            return;
+        }
+        if remaining <= IDLE_WARNING {
+            egui::TopBottomPanel::top("idle_warning").show(ctx, |ui| {
+                ui.colored_label(
+                    ui.visuals().warn_fg_color,
+                    format!(
+                        "Logging out in {}s due to inactivity, move the mouse to stay logged in",
+                        remaining.as_secs()
+                    ),
+                );
+            });
+            ctx.request_repaint_after(Duration::from_secs(1));
+        } else {
+            ctx.request_repaint_after(remaining - IDLE_WARNING);
+        }
+    }
+
+    /// Collects controller input for this frame, opening the controller
+    /// backend when the setting is switched on and closing it when off.
+    fn poll_gamepad(&mut self, ctx: &egui::Context) {
+        self.gamepad_actions.clear();
+        if !self.config.gamepad {
+            self.gamepad = None;
+            return;
+        }
+        if self.gamepad.is_none() {
+            match gamepad::Gamepad::new() {
+                Ok(gamepad) => self.gamepad = Some(gamepad),
+                Err(e) => {
+                    log::error!("Failed to initialise controller support: {e}");
+                    self.config.gamepad = false;
+                    self.show_toast("Controller support is unavailable on this system");
+                    return;
+                }
+            }
+        }
+        if let Some(gamepad) = &mut self.gamepad {
+            self.gamepad_actions = gamepad.poll();
+        }
+        ctx.request_repaint_after(GAMEPAD_POLL_INTERVAL);
+    }
+
+    fn take_gamepad_action(&mut self, action: gamepad::Action) -> bool {
+        let index = self.gamepad_actions.iter().position(|&a| a == action);
+        index.map(|i| self.gamepad_actions.remove(i)).is_some()
+    }
+
+    fn request_certificate(&mut self) {
+        let host = self.tab.get_host().to_owned();
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            sender.send(certificate::fetch(&host)).ok();
+        });
+        self.tab.certificate = None;
+        self.tab.certificate_request = Some(receiver);
+        self.tab.show_certificate = true;
+    }
+
+    fn render_certificate(&mut self, ctx: &egui::Context) {
+        if let Some(receiver) = &self.tab.certificate_request {
+            match receiver.try_recv() {
+                Ok(result) => {
+                    self.tab.certificate = Some(result);
+                    self.tab.certificate_request = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => {
+                    ctx.request_repaint_after(Duration::from_millis(100))
+                }
+                Err(mpsc::TryRecvError::Disconnected) => self.tab.certificate_request = None,
+            }
+        }
+        let host = self.tab.get_host().to_owned();
+        egui::Window::new("Server Certificate")
+            .open(&mut self.tab.show_certificate)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.label(&host);
+                match &self.tab.certificate {
+                    None => {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label("Connecting...");
+                        });
+                    }
+                    Some(Err(e)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, e);
+                    }
+                    Some(Ok(info)) => {
+                        match &info.verification {
+                            Ok(()) => {
+                                ui.colored_label(
+                                    egui::Color32::GREEN,
+                                    "Trusted, valid for this host",
+                                );
+                            }
+                            Err(e) => {
+                                ui.colored_label(
+                                    ui.visuals().error_fg_color,
+                                    format!("Not trusted: {e}"),
+                                );
+                            }
+                        }
+                        egui::Grid::new("certificate").show(ui, |ui| {
+                            ui.label("Subject");
+                            ui.label(&info.subject);
+                            ui.end_row();
+                            ui.label("Issuer");
+                            ui.label(&info.issuer);
+                            ui.end_row();
+                            ui.label("Expires");
+                            match info.expires_in {
+                                Some(left) => ui.label(format!(
+                                    "{} (in {})",
+                                    info.not_after,
+                                    format_duration(left.as_secs() as i64)
+                                )),
+                                None => ui.colored_label(
+                                    ui.visuals().error_fg_color,
+                                    format!("{} (expired)", info.not_after),
+                                ),
+                            };
+                            ui.end_row();
+                        });
+                    }
+                }
+            });
+    }
+
+    fn render_status_bar(&self, ctx: &egui::Context) {
+        egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.style_mut().override_text_style = Some(egui::TextStyle::Small);
+                let size = ui.text_style_height(&egui::TextStyle::Small) * 0.5;
+                let (rect, _) =
+                    ui.allocate_exact_size(egui::vec2(size, size), egui::Sense::hover());
+                ui.painter().circle_filled(
+                    rect.center(),
+                    size * 0.5,
+                    self.tab.server_status.color(),
+                );
+                ui.label(self.tab.server_status.label());
+                ui.separator();
+                self.render_connection_security(ui);
+                ui.label(self.tab.get_host());
+                if matches!(self.tab.state, MhfState::Character) {
+                    ui.separator();
+                    ui.label(&self.tab.username);
+                }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    ui.label(format!("v{LAUNCHER_VERSION}"));
+                });
+            });
+        });
+    }
+
+    fn show_toast(&mut self, text: impl Into<String>) {
+        self.toast = Some((text.into(), Instant::now()));
+    }
+
+    fn render_toast(&mut self, ctx: &egui::Context) {
+        let Some((text, shown_at)) = &self.toast else {
+            return;
+        };
+        let remaining = TOAST_DURATION.saturating_sub(shown_at.elapsed());
+        if remaining.is_zero() {
+            self.toast = None;
+            return;
+        }
+        egui::Area::new("toast")
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-8.0, -8.0))
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.label(text.as_str());
+                });
+            });
+        ctx.request_repaint_after(remaining);
+    }
+
+    fn window_open(&self) -> bool {
+        self.show_settings
+            || self.show_reset_dialog
+            || self.confirm_quit
+            || self.confirm_reset
+            || self.whats_new.is_some()
+            || self.previous_error.is_some()
+            || self.verify_request.is_some()
+            || self.verify_result.is_some()
+            || self.tab.reauth.is_some()
+            || self.tab.pending_start.is_some()
+            || self.tab.confirm_force_close
+            || self.tab.show_certificate
+            || self.tab.pending_motd.is_some()
+            || self.tab.error_dialog.is_some()
+    }
+
+    fn handle_shortcuts(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::CTRL, egui::Key::Q)) {
+            frame.close();
+        }
+        // Esc belongs to a focused field or an open window first.
+        if matches!(self.tab.state, MhfState::Login)
+            && !self.window_open()
+            && ctx.memory(|m| m.focus()).is_none()
+            && ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Escape))
+        {
+            if self.tab.error_message.is_some() {
+                self.tab.error_message = None;
+            } else {
+                frame.close();
+            }
+        }
+    }
+}
+
+impl MhfLauncher {
+    /// Whether an unselected tab is waiting on the game, it can only run once
+    /// per launcher.
+    fn other_tab_launch(&self) -> bool {
+        self.tabs.iter().any(|tab| tab.launch.is_some())
+    }
+
+    fn select(&mut self, index: usize) {
+        if index == self.selected || index >= self.tabs.len() {
+            return;
+        }
+        // Two tabs must not both claim the Discord presence.
+        self.presence = None;
+        mem::swap(&mut self.tab, &mut self.tabs[self.selected]);
+        self.selected = index;
+        mem::swap(&mut self.tab, &mut self.tabs[index]);
+    }
+
+    fn open_tab(&mut self) {
+        let tab = Tab {
+            host: self.tab.host,
+            custom_host: self.tab.custom_host.clone(),
+            ..Default::default()
+        };
+        self.tabs.push(tab);
+        self.select(self.tabs.len() - 1);
+    }
+
+    fn close_tab(&mut self, index: usize) {
+        let launch = if index == self.selected {
+            &self.tab.launch
+        } else {
+            &self.tabs[index].launch
+        };
+        if self.tabs.len() == 1 || launch.is_some() {
+            return;
+        }
+        if index == self.selected {
+            self.select(if index == 0 { 1 } else { index - 1 });
+        }
+        self.tabs.remove(index);
+        if self.selected > index {
+            self.selected -= 1;
+        }
+    }
+
+    /// Lets unselected tabs finish their sign-ins and refreshes and notice
+    /// their game exiting, each is swapped in for the duration.
+    fn poll_background(&mut self, ctx: &egui::Context) {
+        for index in 0..self.tabs.len() {
+            let tab = &self.tabs[index];
+            if index == self.selected
+                || (tab.sign_in.is_none() && tab.refresh.is_none() && tab.launch.is_none())
+            {
+                continue;
+            }
+            mem::swap(&mut self.tab, &mut self.tabs[index]);
+            self.poll_sign_in(ctx);
+            self.poll_refresh(ctx);
+            self.poll_launch();
+            if self.tab.launch.is_some() {
+                ctx.request_repaint_after(Duration::from_secs(1));
+            }
+            mem::swap(&mut self.tab, &mut self.tabs[index]);
+        }
+    }
+
+    fn render_tab_strip(&mut self, ctx: &egui::Context) {
+        let mut select = None;
+        let mut close = None;
+        let mut open = false;
+        egui::TopBottomPanel::top("tabs").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                for index in 0..self.tabs.len() {
+                    let tab = if index == self.selected {
+                        &self.tab
+                    } else {
+                        &self.tabs[index]
+                    };
+                    let label = match tab.state {
+                        MhfState::Character => tab.account_key(),
+                        _ => tab.get_host().to_owned(),
+                    };
+                    let label = if tab.launch.is_some() {
+                        format!("▶ {label}")
+                    } else {
+                        label
+                    };
+                    if ui.selectable_label(index == self.selected, label).clicked() {
+                        select = Some(index);
+                    }
+                    if self.tabs.len() > 1 {
+                        let button =
+                            ui.add_enabled(tab.launch.is_none(), egui::Button::new("×").small());
+                        button.widget_info(|| {
+                            egui::WidgetInfo::labeled(egui::WidgetType::Button, "Close tab")
+                        });
+                        if button
+                            .on_disabled_hover_text("The game is running from this tab")
+                            .clicked()
+                        {
+                            close = Some(index);
+                        }
+                    }
+                    ui.separator();
+                }
+                open = ui
+                    .button("+")
+                    .on_hover_text("Connect to another server")
+                    .clicked();
+            });
+        });
+        if let Some(index) = select {
+            self.select(index);
+        }
+        if let Some(index) = close {
+            self.close_tab(index);
+        }
+        if open {
+            self.open_tab();
+        }
+    }
+}
+
+impl eframe::App for MhfLauncher {
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        if !self.config.mini_mode {
+            self.render_tab_strip(ctx);
+        }
+        self.poll_background(ctx);
+        if self.minimize_on_start {
+            self.minimize_on_start = false;
+            frame.set_minimized(true);
+        }
+        if self.resize_window {
+            self.resize_window = false;
+            frame.set_window_size(if self.config.mini_mode {
+                MINI_WINDOW_SIZE
+            } else {
+                self.config
+                    .window_size
+                    .map_or(DEFAULT_WINDOW_SIZE, Into::into)
+            });
+        }
+        let window_info = &frame.info().window_info;
+        // The mini window's size is never remembered as the normal size.
+        if !window_info.minimized && !window_info.maximized && !self.config.mini_mode {
+            self.config.window_pos = window_info.position.map(Into::into);
+            self.config.window_size = Some(window_info.size.into());
+        }
+        let ui_scale = self
+            .config
+            .ui_scale
+            .clamp(*UI_SCALE_RANGE.start(), *UI_SCALE_RANGE.end());
+        let pixels_per_point = frame.info().native_pixels_per_point.unwrap_or(1.0) * ui_scale;
+        // Rescaling mid-drag would move the slider out from under the pointer.
+        if ctx.pixels_per_point() != pixels_per_point && !ctx.is_using_pointer() {
+            ctx.set_pixels_per_point(pixels_per_point);
+        }
+        let theme = self
+            .config
+            .themes
+            .get(self.tab.get_host())
+            .cloned()
+            .unwrap_or_default();
+        let accent = theme
+            .accent_color
+            .as_deref()
+            .and_then(branding::parse_color)
+            .or_else(|| {
+                self.tab
+                    .auth_data
+                    .branding
+                    .as_ref()
+                    .and_then(Branding::accent)
+            });
+        let density = theme.density.unwrap_or(self.config.density);
+        ctx.style_mut(|style| {
+            for (_, font_id) in style.text_styles.iter_mut() {
+                font_id.size = density.font_size();
+            }
+            density.apply_spacing(&mut style.spacing);
+            style.visuals = match theme.dark_mode {
+                Some(true) => egui::Visuals::dark(),
+                Some(false) => egui::Visuals::light(),
+                None => self.base_visuals.clone(),
+            };
+            if let Some(accent) = accent {
+                branding::apply_accent(&mut style.visuals, accent);
+            }
+        });
+        self.poll_banner(ctx);
+        self.handle_shortcuts(ctx, frame);
+        self.poll_sign_in(ctx);
+        self.poll_refresh(ctx);
+        self.poll_create_character(ctx);
+        self.poll_reauth(ctx);
+        self.poll_launch();
+        self.watch_connection(ctx);
+        self.auto_refresh(ctx, frame.info().window_info.focused);
+        self.update_presence();
+        self.poll_gamepad(ctx);
+        self.handle_idle(ctx);
+        if self.tab.update_required.is_some() {
+            self.render_status_bar(ctx);
+            self.render_update_required(ctx, frame);
+        } else if self.config.mini_mode {
+            self.render_mini(ctx);
+        } else {
+            self.render_status_bar(ctx);
+            self.render_insecure_banner(ctx);
+            if !matches!(self.tab.state, MhfState::Login) {
+                self.tab.login_shown = false;
+            }
+            match self.tab.state {
+                MhfState::Login => self.render_login(ctx),
+                MhfState::Character => self.render_characters(ctx),
+                MhfState::Diagnostics => self.render_diagnostics(ctx),
+                MhfState::Connecting => self.render_connecting(ctx),
+                MhfState::Unreachable => self.render_unreachable(ctx),
+            }
+        }
+        self.render_settings(ctx);
+        self.render_reauth(ctx);
+        self.render_confirm_start(ctx);
+        self.render_force_close(ctx);
+        self.render_confirm_quit(ctx, frame);
+        self.render_confirm_reset(ctx);
+        self.render_certificate(ctx);
+        self.render_motd(ctx);
+        self.render_whats_new(ctx);
+        self.render_verify(ctx);
+        self.render_reset_dialog(ctx);
+        self.render_error_dialog(ctx);
+        self.render_previous_error(ctx);
+        self.render_toast(ctx);
+    }
+
+    fn on_close_event(&mut self) -> bool {
+        // The quit prompt belongs to the tab whose game would be closed.
+        if let Some(index) = self.tabs.iter().position(|tab| tab.launch.is_some()) {
+            self.select(index);
+        }
+        if self.tab.launch.is_none()
+            || !self.config.confirm_quit_while_running
+            || self.quit_confirmed
+        {
+            return true;
+        }
+        self.confirm_quit = true;
+        false
+    }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.save_config();
+        last_error::clear();
+    }
+}
+
+/// Registers the `mhf-launcher://` scheme the first time this executable
+/// runs, and again whenever it has moved.
+#[cfg(windows)]
+fn register_url_scheme(mut config: Config) -> Config {
+    let Ok(exe) = env::current_exe() else {
+        return config;
+    };
+    let exe = exe.display().to_string();
+    if config.url_scheme_handler.as_deref() == Some(exe.as_str()) {
+        return config;
+    }
+    match deeplink::register() {
+        Ok(()) => config.url_scheme_handler = Some(exe),
+        Err(e) => log::warn!("Failed to register {}:// links: {e}", deeplink::SCHEME),
+    }
+    config
+}
+
+fn main() -> Result<(), eframe::Error> {
+    logger::init();
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        last_error::record(&format!("Unexpected failure: {info}"));
+        default_hook(info);
+    }));
+    let config = Config::load();
+    let saved_config = config.to_json().ok();
+    #[cfg(windows)]
+    let config = register_url_scheme(config);
+    let deep_link = deeplink::from_args().map(|url| deeplink::parse(&url));
     let options = eframe::NativeOptions {
-        initial_window_size: Some(egui::vec2(640.0, 480.0)),
+        initial_window_pos: config.window_pos.map(Into::into),
+        initial_window_size: Some(config.window_size.map_or(DEFAULT_WINDOW_SIZE, Into::into)),
+        active: !config.start_minimized,
         ..Default::default()
     };
     eframe::run_native(
         "My egui App",
         options,
-        Box::new(|_cc| {
-            let mut l = Box::<MhfLauncher>::default();
-            l.username = "rockisch".into();
-            l.password = "abcdef".into();
+        Box::new(|cc| {
+            let mut l = Box::new(MhfLauncher {
+                tabs: vec![Tab::default()],
+                ..Default::default()
+            });
+            l.base_visuals = cc.egui_ctx.style().visuals.clone();
+            fonts::install_cjk_fallback(&cc.egui_ctx);
+            // Test account for local servers, release builds start with empty fields.
+            #[cfg(debug_assertions)]
+            {
+                l.tab.username = "rockisch".into();
+                l.tab.password = "abcdef".into();
+            }
+            l.tab.host = config.host;
+            l.tab.custom_host = config.custom_host.clone();
+            l.minimize_on_start = config.start_minimized;
+            l.resize_window = config.mini_mode;
+            l.config = config;
+            l.saved_config = saved_config;
+            match deep_link {
+                Some(Ok(link)) => l.open_deep_link(link),
+                Some(Err(e)) => {
+                    log::warn!("Ignoring launcher link: {e}");
+                    l.show_toast(e);
+                }
+                None => {}
+            }
+            l.validate_mhf_folder();
+            if !Config::is_persistent() {
+                log::warn!("No writable location for the config, settings are kept in memory");
+                l.tab.error_message = Some(
+                    "Settings can't be saved on this system and will be lost when the launcher closes"
+                        .into(),
+                );
+            }
+            l.check_whats_new();
+            l.previous_error = last_error::take();
+            l.restore_session();
             l
         }),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn character(id: u32, name: &str) -> Character {
+        Character {
+            id,
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    fn launch_data(auth_data: AuthData) -> LaunchData {
+        LaunchData {
+            auth_data,
+            username: "hunter".into(),
+            password: "secret".into(),
+            mhf_folder: "C:/MHF".into(),
+        }
+    }
+
+    #[test]
+    fn build_config_collects_char_ids() {
+        let data = launch_data(AuthData {
+            characters: vec![character(1, "A"), character(7, "B"), character(3, "C")],
+            ..Default::default()
+        });
+        let selected = character(7, "B");
+        let config = data.build_config(&selected);
+        assert_eq!(config.char_ids, [1, 7, 3]);
+        assert_eq!(config.char_id, 7);
+        assert_eq!(config.char_name, "B");
+        assert_eq!(config.user_name, "hunter");
+        assert_eq!(config.user_password, "secret");
+    }
+
+    #[test]
+    fn build_config_maps_mez_fes() {
+        let data = launch_data(AuthData {
+            mez_fes: Some(MezFes {
+                id: 4,
+                start: 100,
+                end: 200,
+                solo_tickets: 5,
+                group_tickets: 6,
+                stalls: vec![3, u32::MAX],
+            }),
+            ..Default::default()
+        });
+        let selected = character(1, "A");
+        let config = data.build_config(&selected);
+        assert_eq!(config.mez_event_id, 4);
+        assert_eq!(config.mez_start, 100);
+        assert_eq!(config.mez_end, 200);
+        assert_eq!(config.mez_solo_tickets, 5);
+        assert_eq!(config.mez_group_tickets, 6);
+        // 3 is a known stall, unknown values are skipped instead of failing the launch.
+        assert_eq!(config.mez_stalls.len(), 1);
+    }
+
+    #[test]
+    fn build_config_without_mez_fes() {
+        let data = launch_data(AuthData::default());
+        let selected = character(1, "A");
+        let config = data.build_config(&selected);
+        assert_eq!(config.mez_event_id, 0);
+        assert_eq!(config.mez_start, 0);
+        assert_eq!(config.mez_end, 0);
+        assert!(config.mez_stalls.is_empty());
+    }
+
+    #[test]
+    fn minimal_user_uses_defaults() {
+        let user: User = serde_json::from_str(r#"{"token": "t"}"#).unwrap();
+        assert_eq!(user.token, "t");
+        assert_eq!(user.rights, 0);
+    }
+
+    #[test]
+    fn minimal_character_uses_defaults() {
+        let character: Character = serde_json::from_str(r#"{"id": 1, "name": "A"}"#).unwrap();
+        assert_eq!(character.id, 1);
+        assert_eq!(character.name, "A");
+        assert!(!character.is_new);
+        assert!(!character.is_female);
+        assert_eq!(
+            (
+                character.weapon,
+                character.hr,
+                character.gr,
+                character.last_login
+            ),
+            (0, 0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn minimal_mez_fes_uses_defaults() {
+        let mez_fes: MezFes = serde_json::from_str(r#"{"id": 1, "start": 10, "end": 20}"#).unwrap();
+        assert_eq!((mez_fes.id, mez_fes.start, mez_fes.end), (1, 10, 20));
+        assert_eq!((mez_fes.solo_tickets, mez_fes.group_tickets), (0, 0));
+        assert!(mez_fes.stalls.is_empty());
+    }
+
+    #[test]
+    fn minimal_auth_data_uses_defaults() {
+        let auth_data: AuthData =
+            serde_json::from_str(r#"{"currentTs": 5, "user": {"token": "t"}}"#).unwrap();
+        assert_eq!(auth_data.current_ts, 5);
+        assert_eq!((auth_data.expiry_ts, auth_data.entrance_count), (0, 0));
+        assert!(auth_data.notifications.is_empty());
+        assert!(auth_data.characters.is_empty());
+        assert!(auth_data.mez_fes.is_none());
+        assert!(auth_data.branding.is_none());
+        assert!(auth_data.limits.is_none());
+        assert!(auth_data.char_limit.is_none());
+        assert!(auth_data.motd.is_none());
+    }
+
+    #[test]
+    fn auth_data_ignores_unknown_fields() {
+        let auth_data: AuthData = serde_json::from_str(
+            r#"{"currentTs": 5, "user": {"token": "t", "extra": 1}, "newField": [1, 2]}"#,
+        )
+        .unwrap();
+        assert_eq!(auth_data.user.token, "t");
+    }
+
+    #[test]
+    fn sign_in_without_token_is_rejected() {
+        let mut launcher = MhfLauncher::default();
+        let accepted = launcher.accept_sign_in(AuthData {
+            characters: vec![character(1, "A")],
+            ..Default::default()
+        });
+        assert!(!accepted);
+        assert!(matches!(launcher.tab.state, MhfState::Login));
+        assert!(launcher.tab.auth_data.characters.is_empty());
+        assert!(launcher.tab.auth_data.user.token.is_empty());
+        assert_eq!(
+            launcher.tab.error_message.as_deref(),
+            Some("Login incomplete — no session token received")
+        );
+    }
+
+    fn weapon_from(json: &str) -> Result<u32, serde_json::Error> {
+        serde_json::from_str::<Character>(json).map(|c| c.weapon)
+    }
+
+    #[test]
+    fn lenient_u32_accepts_numbers_and_strings() {
+        assert_eq!(
+            weapon_from(r#"{"id": 1, "name": "A", "weapon": 12}"#).unwrap(),
+            12
+        );
+        assert_eq!(
+            weapon_from(r#"{"id": 1, "name": "A", "weapon": "12"}"#).unwrap(),
+            12
+        );
+        assert_eq!(
+            weapon_from(r#"{"id": 1, "name": "A", "weapon": " 12 "}"#).unwrap(),
+            12
+        );
+        assert_eq!(weapon_from(r#"{"id": 1, "name": "A"}"#).unwrap(), 0);
+    }
+
+    #[test]
+    fn lenient_u32_rejects_non_numeric_strings() {
+        assert!(weapon_from(r#"{"id": 1, "name": "A", "weapon": "abc"}"#).is_err());
+    }
+
+    #[test]
+    fn camel_case_keys_renames_nested_objects_and_arrays() {
+        let value = camel_case_keys(serde_json::json!({
+            "mez_fes": {"solo_tickets": 1},
+            "characters": [{"is_new": true, "last_login": 2}],
+        }));
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "mezFes": {"soloTickets": 1},
+                "characters": [{"isNew": true, "lastLogin": 2}],
+            })
+        );
+    }
+
+    #[test]
+    fn camel_case_keeps_camel_case_keys() {
+        assert_eq!(camel_case("currentTs"), "currentTs");
+        assert_eq!(camel_case("id"), "id");
+    }
+
+    #[test]
+    fn camel_case_handles_odd_underscores() {
+        assert_eq!(camel_case("_private_key"), "_privateKey");
+        assert_eq!(camel_case("__id"), "__id");
+        assert_eq!(camel_case("solo__tickets"), "soloTickets");
+        assert_eq!(camel_case("trailing_"), "trailing_");
+    }
+
+    #[test]
+    fn snake_case_auth_data_decodes() {
+        let value = camel_case_keys(serde_json::json!({
+            "current_ts": 5,
+            "expiry_ts": 6,
+            "user": {"token": "t", "rights": 2},
+            "characters": [{"id": 1, "name": "A", "is_new": true, "hr": "3"}],
+            "mez_fes": {"id": 1, "start": 10, "end": 20, "group_tickets": 4},
+        }));
+        let auth_data: AuthData = decode_response(&value, false).unwrap();
+        assert_eq!((auth_data.current_ts, auth_data.expiry_ts), (5, 6));
+        assert_eq!(auth_data.user.rights, 2);
+        assert!(auth_data.characters[0].is_new);
+        assert_eq!(auth_data.characters[0].hr, 3);
+        assert_eq!(auth_data.mez_fes.unwrap().group_tickets, 4);
+    }
+
+    #[test]
+    fn camel_case_auth_data_decodes() {
+        let value = serde_json::json!({
+            "currentTs": 5,
+            "user": {"token": "t"},
+            "characters": [{"id": 1, "name": "A", "isNew": true}],
+        });
+        let auth_data: AuthData = decode_response(&value, false).unwrap();
+        assert_eq!(auth_data.current_ts, 5);
+        assert!(auth_data.characters[0].is_new);
+    }
+
+    #[test]
+    fn http_date_parses() {
+        assert_eq!(
+            parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT"),
+            Some(784_111_777)
+        );
+        assert_eq!(
+            parse_http_date("Thu, 29 Feb 2024 00:00:00 GMT"),
+            Some(1_709_164_800)
+        );
+        assert_eq!(parse_http_date("Sun, 06 Nov 1994 08:49:37 PST"), None);
+        assert_eq!(parse_http_date("120"), None);
+    }
+}
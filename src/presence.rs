@@ -0,0 +1,98 @@
+use std::{
+    sync::mpsc,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use discord_rich_presence::{
+    activity::{self, Timestamps},
+    DiscordIpc, DiscordIpcClient,
+};
+
+/// How long to wait before trying Discord again when it isn't running.
+const RETRY_INTERVAL: Duration = Duration::from_secs(15);
+
+#[derive(Clone, PartialEq)]
+pub enum Activity {
+    Login,
+    Browsing { host: String },
+    Playing { character: String, hr: u32 },
+}
+
+/// Discord Rich Presence, updated from a background thread since the IPC
+/// calls block. Without Discord running every update is silently dropped.
+/// Dropping this clears the presence.
+pub struct Presence {
+    sender: mpsc::Sender<Activity>,
+    last: Option<Activity>,
+}
+
+impl Presence {
+    pub fn new(application_id: String) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || run(&application_id, receiver));
+        Self { sender, last: None }
+    }
+
+    pub fn set(&mut self, activity: Activity) {
+        if self.last.as_ref() != Some(&activity) {
+            self.sender.send(activity.clone()).ok();
+            self.last = Some(activity);
+        }
+    }
+}
+
+fn run(application_id: &str, receiver: mpsc::Receiver<Activity>) {
+    let mut client = DiscordIpcClient::new(application_id);
+    let mut connected = false;
+    let mut current = None;
+    let mut playing_since = 0;
+    loop {
+        match receiver.recv_timeout(RETRY_INTERVAL) {
+            Ok(activity) => {
+                if matches!(activity, Activity::Playing { .. })
+                    && !matches!(current, Some(Activity::Playing { .. }))
+                {
+                    playing_since = SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .map_or(0, |d| d.as_secs() as i64);
+                }
+                current = Some(activity);
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) if connected => continue,
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+        if !connected {
+            connected = client.connect().is_ok();
+        }
+        let Some(activity) = current.as_ref().filter(|_| connected) else {
+            continue;
+        };
+        let result = match activity {
+            Activity::Login => {
+                client.set_activity(activity::Activity::new().state("In the launcher"))
+            }
+            Activity::Browsing { host } => client.set_activity(
+                activity::Activity::new()
+                    .state("Choosing a character")
+                    .details(host.as_str()),
+            ),
+            Activity::Playing { character, hr } => client.set_activity(
+                activity::Activity::new()
+                    .state(format!("HR {hr}"))
+                    .details(format!("Playing as {character}"))
+                    .timestamps(Timestamps::new().start(playing_since)),
+            ),
+        };
+        if let Err(e) = result {
+            log::warn!("Discord presence update failed, retrying later: {e}");
+            client.close().ok();
+            connected = false;
+        }
+    }
+    if connected {
+        client.clear_activity().ok();
+        client.close().ok();
+    }
+}
@@ -0,0 +1,127 @@
+use serde_json::Value;
+
+/// Expected JSON shape of a server response, used in developer mode to point
+/// at the exact field that failed to decode instead of serde's generic error.
+pub enum Kind {
+    Any,
+    Bool,
+    Unsigned,
+    Integer,
+    String,
+    Array(&'static Kind),
+    Object(&'static [Field]),
+    Either(&'static Kind, &'static Kind),
+}
+
+pub struct Field {
+    pub name: &'static str,
+    pub kind: Kind,
+    pub required: bool,
+}
+
+impl Field {
+    pub const fn required(name: &'static str, kind: Kind) -> Self {
+        Self {
+            name,
+            kind,
+            required: true,
+        }
+    }
+
+    pub const fn optional(name: &'static str, kind: Kind) -> Self {
+        Self {
+            name,
+            kind,
+            required: false,
+        }
+    }
+}
+
+/// Implemented by every type the launcher decodes from a server response.
+pub trait Shape {
+    const SHAPE: Kind;
+}
+
+/// Lists every mismatch between `value` and `kind`, e.g.
+/// `characters[0].hr expected unsigned integer, got string`.
+pub fn check(value: &Value, kind: &Kind) -> Vec<String> {
+    let mut problems = Vec::new();
+    check_at(value, kind, "response", &mut problems);
+    problems
+}
+
+fn check_at(value: &Value, kind: &Kind, path: &str, problems: &mut Vec<String>) {
+    if !matches(value, kind) {
+        problems.push(format!(
+            "{path} expected {}, got {}",
+            describe(kind),
+            type_of(value)
+        ));
+        return;
+    }
+    match (kind, value) {
+        (Kind::Array(item), Value::Array(items)) => {
+            for (i, value) in items.iter().enumerate() {
+                check_at(value, item, &format!("{path}[{i}]"), problems);
+            }
+        }
+        (Kind::Object(fields), Value::Object(map)) => {
+            for field in fields.iter() {
+                let field_path = if path == "response" {
+                    field.name.to_owned()
+                } else {
+                    format!("{path}.{}", field.name)
+                };
+                match map.get(field.name) {
+                    None | Some(Value::Null) if !field.required => {}
+                    None => problems.push(format!("{field_path} is missing")),
+                    Some(value) => check_at(value, &field.kind, &field_path, problems),
+                }
+            }
+        }
+        (Kind::Either(a, b), value) => {
+            let kind = if matches(value, a) { a } else { b };
+            check_at(value, kind, path, problems);
+        }
+        _ => {}
+    }
+}
+
+fn matches(value: &Value, kind: &Kind) -> bool {
+    match kind {
+        Kind::Any => true,
+        Kind::Bool => value.is_boolean(),
+        Kind::Unsigned => value.is_u64(),
+        Kind::Integer => value.is_i64() || value.is_u64(),
+        Kind::String => value.is_string(),
+        Kind::Array(_) => value.is_array(),
+        Kind::Object(_) => value.is_object(),
+        Kind::Either(a, b) => matches(value, a) || matches(value, b),
+    }
+}
+
+fn describe(kind: &Kind) -> String {
+    match kind {
+        Kind::Any => "anything".into(),
+        Kind::Bool => "boolean".into(),
+        Kind::Unsigned => "unsigned integer".into(),
+        Kind::Integer => "integer".into(),
+        Kind::String => "string".into(),
+        Kind::Array(_) => "array".into(),
+        Kind::Object(_) => "object".into(),
+        Kind::Either(a, b) => format!("{} or {}", describe(a), describe(b)),
+    }
+}
+
+fn type_of(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "decimal number",
+        Value::Number(n) if n.is_i64() && !n.is_u64() => "negative integer",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}